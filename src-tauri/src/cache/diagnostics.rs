@@ -0,0 +1,115 @@
+use crate::cache::game_state::{compute_game_state, GameState};
+use crate::cache::storage::read_versions;
+use crate::installer::Game;
+use crate::searcher::GameInfo;
+use serde::Serialize;
+use std::path::PathBuf;
+
+// Per-game summary produced by `scan_installed`: enough to tell at a glance
+// whether a game's unlocker needs attention, without re-running the scan or
+// re-reading its manifest.
+#[derive(Serialize, Debug, Clone)]
+pub struct GameReport {
+    pub title: String,
+    pub path: String,
+    pub native: bool,
+    pub state: GameState,
+}
+
+// Scan every Steam library under `steam_library_paths` and compute each
+// installed game's unlocker state in one pass, for a library-wide health
+// check instead of checking games one at a time.
+pub async fn scan_installed(steam_library_paths: &[PathBuf]) -> Result<Vec<GameReport>, String> {
+    let cached_versions = read_versions()?;
+    let games_info = crate::searcher::find_installed_games(steam_library_paths, false).await;
+
+    Ok(games_info
+        .into_iter()
+        .map(|game_info| {
+            let game = to_game(game_info);
+            let state = compute_game_state(&game, &cached_versions);
+            GameReport {
+                title: game.title,
+                path: game.path,
+                native: game.native,
+                state,
+            }
+        })
+        .collect())
+}
+
+// Adapt a freshly-scanned `GameInfo` into the `Game` shape `compute_game_state`
+// expects, mirroring the conversion `scan_steam_games` does when building app
+// state.
+fn to_game(info: GameInfo) -> Game {
+    Game {
+        id: info.id,
+        title: info.title,
+        path: info.path.to_string_lossy().to_string(),
+        native: info.native,
+        api_files: info.api_files,
+        cream_installed: info.cream_installed,
+        smoke_installed: info.smoke_installed,
+        installing: false,
+        source: info.source,
+        compat_tool: info.compat_tool,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::storage::{CacheVersions, VersionInfo};
+    use crate::searcher::GameSource;
+
+    fn versions() -> CacheVersions {
+        CacheVersions {
+            smokeapi: VersionInfo {
+                active: "v1.0.0".to_string(),
+                sha256: None,
+                retained: vec!["v1.0.0".to_string()],
+            },
+            creamlinux: VersionInfo {
+                active: "v2.0.0".to_string(),
+                sha256: None,
+                retained: vec!["v2.0.0".to_string()],
+            },
+        }
+    }
+
+    fn game_info(native: bool) -> GameInfo {
+        GameInfo {
+            id: "123".to_string(),
+            title: "Test Game".to_string(),
+            path: PathBuf::from("/nonexistent/test-game"),
+            native,
+            api_files: vec![],
+            cream_installed: false,
+            smoke_installed: false,
+            source: GameSource::Steam,
+            compat_tool: None,
+        }
+    }
+
+    #[test]
+    fn test_to_game_preserves_fields_and_clears_installing() {
+        let game = to_game(game_info(true));
+        assert_eq!(game.title, "Test Game");
+        assert_eq!(game.path, "/nonexistent/test-game");
+        assert!(game.native);
+        assert!(!game.installing);
+    }
+
+    #[test]
+    fn test_game_report_reflects_not_installed_state() {
+        let game = to_game(game_info(true));
+        let state = compute_game_state(&game, &versions());
+        let report = GameReport {
+            title: game.title,
+            path: game.path,
+            native: game.native,
+            state,
+        };
+        assert_eq!(report.state, GameState::NotInstalled);
+    }
+}
@@ -0,0 +1,143 @@
+use crate::installer::DlcInfo;
+use log::{info, warn};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::storage::get_cache_dir;
+
+// How long a cached DLC list is considered fresh before we re-hit the Steam store
+pub const DEFAULT_DLC_CACHE_TTL_SECS: u64 = 7 * 24 * 60 * 60;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct DlcCacheEntry {
+    dlcs: Vec<DlcInfo>,
+    // When this entry was fetched; also doubles as the freshness marker used
+    // to decide whether the app's DLC list might have changed since.
+    fetched_at: u64,
+}
+
+// In-memory mirror of dlc_cache.json, populated on first access. Write-through
+// front for the on-disk store so repeated lookups in a session don't re-read
+// and re-parse the whole cache file every time.
+static DLC_CACHE: OnceLock<Mutex<HashMap<String, DlcCacheEntry>>> = OnceLock::new();
+
+fn dlc_cache() -> &'static Mutex<HashMap<String, DlcCacheEntry>> {
+    DLC_CACHE.get_or_init(|| Mutex::new(read_dlc_cache_from_disk()))
+}
+
+fn get_dlc_cache_path() -> Result<PathBuf, String> {
+    Ok(get_cache_dir()?.join("dlc_cache.json"))
+}
+
+fn read_dlc_cache_from_disk() -> HashMap<String, DlcCacheEntry> {
+    let path = match get_dlc_cache_path() {
+        Ok(p) => p,
+        Err(e) => {
+            warn!("Failed to resolve DLC cache path: {}", e);
+            return HashMap::new();
+        }
+    };
+
+    if !path.exists() {
+        return HashMap::new();
+    }
+
+    match fs::read_to_string(&path).ok().and_then(|content| {
+        serde_json::from_str::<HashMap<String, DlcCacheEntry>>(&content).ok()
+    }) {
+        Some(entries) => entries,
+        None => {
+            warn!("DLC cache at {} is unreadable, starting fresh", path.display());
+            HashMap::new()
+        }
+    }
+}
+
+fn write_dlc_cache(entries: &HashMap<String, DlcCacheEntry>) -> Result<(), String> {
+    let path = get_dlc_cache_path()?;
+
+    let content = serde_json::to_string_pretty(entries)
+        .map_err(|e| format!("Failed to serialize DLC cache: {}", e))?;
+
+    fs::write(&path, content).map_err(|e| format!("Failed to write DLC cache: {}", e))?;
+
+    Ok(())
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+// Return the cached DLC list for an app_id if it is younger than `ttl_secs`
+pub fn load_cached_dlcs(app_id: &str, ttl_secs: u64) -> Option<Vec<DlcInfo>> {
+    let cache = dlc_cache().lock();
+    let entry = cache.get(app_id)?;
+
+    let age = now_secs().saturating_sub(entry.fetched_at);
+    if age > ttl_secs {
+        info!(
+            "DLC cache entry for {} is stale ({}s old), refreshing",
+            app_id, age
+        );
+        return None;
+    }
+
+    info!(
+        "Using cached DLC metadata for {} ({} DLCs, {}s old)",
+        app_id,
+        entry.dlcs.len(),
+        age
+    );
+    Some(entry.dlcs.clone())
+}
+
+// Store (or refresh) the DLC list for an app_id, updating the in-memory
+// cache and persisting the full table to disk.
+//
+// Merges with whatever was cached before (union of appids, refreshing each
+// one's name/owned state from `dlcs`) instead of replacing the entry
+// outright, since the Steam store endpoint intermittently drops DLC IDs
+// from its response - a plain replace would make those entries disappear
+// just because one refresh happened to miss them.
+pub fn cache_dlcs(app_id: &str, dlcs: &[DlcInfo]) -> Result<(), String> {
+    let mut cache = dlc_cache().lock();
+
+    let mut merged: Vec<DlcInfo> = cache
+        .get(app_id)
+        .map(|entry| entry.dlcs.clone())
+        .unwrap_or_default();
+
+    for fresh in dlcs {
+        match merged.iter_mut().find(|d| d.appid == fresh.appid) {
+            Some(existing) => *existing = fresh.clone(),
+            None => merged.push(fresh.clone()),
+        }
+    }
+
+    let merged_count = merged.len();
+
+    cache.insert(
+        app_id.to_string(),
+        DlcCacheEntry {
+            dlcs: merged,
+            fetched_at: now_secs(),
+        },
+    );
+
+    write_dlc_cache(&cache)?;
+    info!(
+        "Cached {} DLCs for app {} ({} newly fetched)",
+        merged_count,
+        app_id,
+        dlcs.len()
+    );
+    Ok(())
+}
@@ -0,0 +1,220 @@
+use crate::cache::storage::CacheVersions;
+use crate::installer::Game;
+use serde::Serialize;
+use std::path::Path;
+
+// Queryable install/update status for a single game's relevant unlocker,
+// computed from its manifest and the cached "latest" versions. Lets the
+// frontend color-code each entry without re-running the update-check logic
+// itself (inspired by anime-launcher-sdk's launcher state pattern).
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "status")]
+pub enum GameState {
+    NotInstalled,
+    UpToDate,
+    UpdateAvailable { current: String, latest: String },
+    // The manifest says an unlocker is installed but its backup of the
+    // original API DLL is missing, so uninstalling would destroy the only
+    // copy of the original file.
+    BackupCorrupted,
+    // The manifest records a version installed, but the files that version
+    // should have placed on disk are gone (e.g. a user deleted them by
+    // hand), so the recorded version can't be trusted.
+    Corrupted,
+    // The manifest records the wrong unlocker for this game: SmokeAPI on a
+    // native Linux game, or CreamLinux on a non-native one. Most likely the
+    // game's native-ness changed (e.g. a Proton override) after install.
+    NativeMismatch,
+}
+
+// Compute the state of whichever unlocker applies to `game`: CreamLinux for
+// native Linux games, SmokeAPI for everything else.
+pub fn compute_game_state(game: &Game, cached_versions: &CacheVersions) -> GameState {
+    let manifest = crate::cache::read_manifest(&game.path).unwrap_or_default();
+
+    if game.native {
+        if manifest.smokeapi_version.is_some() {
+            return GameState::NativeMismatch;
+        }
+        if manifest.creamlinux_version.is_some() && !has_creamlinux_binaries(game) {
+            return GameState::Corrupted;
+        }
+        return state_for_versions(manifest.creamlinux_version, &cached_versions.creamlinux.active);
+    }
+
+    if manifest.creamlinux_version.is_some() {
+        return GameState::NativeMismatch;
+    }
+
+    if manifest.smokeapi_version.is_some() {
+        if !has_intact_backup(game) {
+            return GameState::BackupCorrupted;
+        }
+        if !has_smokeapi_files(game) {
+            return GameState::Corrupted;
+        }
+    }
+
+    state_for_versions(manifest.smokeapi_version, &cached_versions.smokeapi.active)
+}
+
+fn state_for_versions(installed: Option<String>, latest: &str) -> GameState {
+    match installed {
+        None => GameState::NotInstalled,
+        Some(version) if version != latest => GameState::UpdateAvailable {
+            current: version,
+            latest: latest.to_string(),
+        },
+        Some(_) => GameState::UpToDate,
+    }
+}
+
+// Check that every file CreamLinux's installer writes (the same list
+// `uninstall_from_game` enumerates) is still present in the game directory.
+fn has_creamlinux_binaries(game: &Game) -> bool {
+    crate::unlockers::creamlinux::CREAMLINUX_BINARIES
+        .iter()
+        .all(|file| Path::new(&game.path).join(file).exists())
+}
+
+// Check that every API file SmokeAPI installs over is still present in the
+// game directory. SmokeAPI writes its DLL directly at each api_file path
+// (after backing up the original), so a missing entry means the install was
+// tampered with or partially deleted after the fact.
+fn has_smokeapi_files(game: &Game) -> bool {
+    game.api_files
+        .iter()
+        .all(|api_file| Path::new(&game.path).join(api_file).exists())
+}
+
+// Check that at least one of the game's API files has a surviving backup:
+// the `<name>.install_bak` copy SmokeAPI's install transaction keeps, or a
+// `steam_api*_o.dll` left by installs made before manifest tracking.
+fn has_intact_backup(game: &Game) -> bool {
+    if game.api_files.is_empty() {
+        return true;
+    }
+
+    game.api_files.iter().any(|api_file| {
+        let path = Path::new(&game.path).join(api_file);
+        let backup_name = path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .replace(".dll", "_o.dll");
+        let mut install_bak = path.clone().into_os_string();
+        install_bak.push(".install_bak");
+        path.with_file_name(backup_name).exists() || Path::new(&install_bak).exists()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::storage::VersionInfo;
+    use crate::searcher::GameSource;
+
+    fn versions() -> CacheVersions {
+        CacheVersions {
+            smokeapi: VersionInfo {
+                active: "v1.0.0".to_string(),
+                sha256: None,
+                retained: vec!["v1.0.0".to_string()],
+            },
+            creamlinux: VersionInfo {
+                active: "v2.0.0".to_string(),
+                sha256: None,
+                retained: vec!["v2.0.0".to_string()],
+            },
+        }
+    }
+
+    fn game(native: bool, api_files: Vec<String>) -> Game {
+        Game {
+            id: "123".to_string(),
+            title: "Test Game".to_string(),
+            path: "/nonexistent/test-game".to_string(),
+            native,
+            api_files,
+            cream_installed: false,
+            smoke_installed: false,
+            installing: false,
+            source: GameSource::Steam,
+            compat_tool: None,
+        }
+    }
+
+    #[test]
+    fn test_not_installed_when_no_manifest() {
+        let state = compute_game_state(&game(true, vec![]), &versions());
+        assert_eq!(state, GameState::NotInstalled);
+    }
+
+    #[test]
+    fn test_state_for_versions_reports_update_available() {
+        let state = state_for_versions(Some("v0.9.0".to_string()), "v1.0.0");
+        assert_eq!(
+            state,
+            GameState::UpdateAvailable {
+                current: "v0.9.0".to_string(),
+                latest: "v1.0.0".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_state_for_versions_reports_up_to_date() {
+        let state = state_for_versions(Some("v1.0.0".to_string()), "v1.0.0");
+        assert_eq!(state, GameState::UpToDate);
+    }
+
+    #[test]
+    fn test_has_intact_backup_is_true_with_no_api_files() {
+        assert!(has_intact_backup(&game(false, vec![])));
+    }
+
+    #[test]
+    fn test_native_mismatch_when_smokeapi_recorded_for_native_game() {
+        use crate::cache::version::GameManifest;
+        use std::fs;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().to_string_lossy().to_string();
+        crate::cache::version::write_manifest(&path, &GameManifest::with_smokeapi("v1.0.0".to_string())).unwrap();
+
+        let mut g = game(true, vec![]);
+        g.path = path;
+        assert_eq!(compute_game_state(&g, &versions()), GameState::NativeMismatch);
+    }
+
+    #[test]
+    fn test_corrupted_when_creamlinux_binaries_missing() {
+        use crate::cache::version::GameManifest;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().to_string_lossy().to_string();
+        crate::cache::version::write_manifest(&path, &GameManifest::with_creamlinux("v2.0.0".to_string())).unwrap();
+
+        let mut g = game(true, vec![]);
+        g.path = path;
+        assert_eq!(compute_game_state(&g, &versions()), GameState::Corrupted);
+    }
+
+    #[test]
+    fn test_corrupted_when_smokeapi_dll_missing() {
+        use crate::cache::version::GameManifest;
+        use std::fs;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().to_string_lossy().to_string();
+        crate::cache::version::write_manifest(&path, &GameManifest::with_smokeapi("v1.0.0".to_string())).unwrap();
+
+        // A surviving backup makes the install pass `has_intact_backup`, but
+        // the DLL itself is still missing - that's what `Corrupted` catches.
+        fs::write(Path::new(&path).join("steam_api64_o.dll"), b"").unwrap();
+
+        let mut g = game(false, vec!["steam_api64.dll".to_string()]);
+        g.path = path;
+        assert_eq!(compute_game_state(&g, &versions()), GameState::Corrupted);
+    }
+}
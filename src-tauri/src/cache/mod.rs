@@ -1,25 +1,99 @@
+mod diagnostics;
+mod dlc;
+mod game_state;
+mod scan;
 mod storage;
 mod version;
 
+pub use diagnostics::{scan_installed, GameReport};
+
+pub use dlc::{cache_dlcs, load_cached_dlcs, DEFAULT_DLC_CACHE_TTL_SECS};
+
+pub use game_state::{compute_game_state, GameState};
+
+pub use scan::{get_cached_scanned_game, prune_missing_games, store_scanned_game};
+
 pub use storage::{
-    get_creamlinux_version_dir, get_smokeapi_version_dir, is_cache_initialized,
-    list_creamlinux_files, list_smokeapi_dlls, read_versions, update_creamlinux_version,
-    update_smokeapi_version,
+    get_cache_dir, get_creamlinux_version_dir, get_smokeapi_version_dir, is_cache_initialized,
+    list_cached_versions, list_creamlinux_files, list_smokeapi_dlls, read_versions,
+    update_creamlinux_version, update_smokeapi_version, use_version,
 };
 
 pub use version::{
     read_manifest, remove_creamlinux_version, remove_smokeapi_version,
     update_creamlinux_version as update_game_creamlinux_version,
-    update_smokeapi_version as update_game_smokeapi_version,
+    update_smokeapi_version as update_game_smokeapi_version, VersionComparison,
 };
 
-use crate::unlockers::{CreamLinux, SmokeAPI, Unlocker};
+use crate::unlockers::{CreamLinux, ProgressSender, SmokeAPI, Unlocker, UnlockerError};
 use log::{error, info, warn};
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
 use std::collections::HashMap;
+use thiserror::Error;
+
+// Structured error type for the cache layer's maintenance operations
+// (initializing the cache, checking for updates, updating outdated games),
+// mirroring `InstallerError`'s shape so callers can branch on `code()`.
+#[derive(Debug, Error)]
+pub enum CacheError {
+    #[error(transparent)]
+    Unlocker(#[from] UnlockerError),
+
+    #[error("{0}")]
+    Other(String),
+}
+
+impl CacheError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            CacheError::Unlocker(e) => e.code(),
+            CacheError::Other(_) => "other",
+        }
+    }
+}
+
+impl From<CacheError> for String {
+    fn from(err: CacheError) -> Self {
+        err.to_string()
+    }
+}
+
+impl From<String> for CacheError {
+    fn from(s: String) -> Self {
+        CacheError::Other(s)
+    }
+}
+
+impl Serialize for CacheError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("CacheError", 2)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
+
+// Load the version pins from config, falling back to "always latest" if the
+// config can't be read — a missing/corrupt config shouldn't block startup.
+fn load_version_pins() -> (Option<String>, Option<String>) {
+    match crate::config::load_config() {
+        Ok(config) => (config.smokeapi_version_pin, config.creamlinux_version_pin),
+        Err(e) => {
+            warn!("Failed to load config for version pins, defaulting to latest: {}", e);
+            (None, None)
+        }
+    }
+}
 
 // Initialize the cache on app startup
-// Downloads both unlockers if they don't exist
-pub async fn initialize_cache() -> Result<(), String> {
+// Downloads both unlockers if they don't exist. `progress` is forwarded to
+// both downloads so a caller can stream download progress to the UI. Honors
+// any version pins set in config, falling back to latest when unpinned.
+pub async fn initialize_cache(progress: Option<ProgressSender>) -> Result<(), CacheError> {
     info!("Initializing cache...");
 
     // Check if cache is already initialized
@@ -30,27 +104,33 @@ pub async fn initialize_cache() -> Result<(), String> {
 
     info!("Cache not initialized, downloading unlockers...");
 
+    let (smokeapi_pin, creamlinux_pin) = load_version_pins();
+
     // Download SmokeAPI
-    match SmokeAPI::download_to_cache().await {
-        Ok(version) => {
-            info!("Downloaded SmokeAPI version: {}", version);
-            update_smokeapi_version(&version)?;
+    let smokeapi_version = SmokeAPI::resolve_target_version(smokeapi_pin.as_deref()).await?;
+
+    match SmokeAPI::download_to_cache(&smokeapi_version, progress.clone()).await {
+        Ok(downloaded) => {
+            info!("Downloaded SmokeAPI version: {}", downloaded.version);
+            update_smokeapi_version(&downloaded.version, downloaded.sha256)?;
         }
         Err(e) => {
             error!("Failed to download SmokeAPI: {}", e);
-            return Err(format!("Failed to download SmokeAPI: {}", e));
+            return Err(e.into());
         }
     }
 
     // Download CreamLinux
-    match CreamLinux::download_to_cache().await {
-        Ok(version) => {
-            info!("Downloaded CreamLinux version: {}", version);
-            update_creamlinux_version(&version)?;
+    let creamlinux_version = CreamLinux::resolve_target_version(creamlinux_pin.as_deref()).await?;
+
+    match CreamLinux::download_to_cache(&creamlinux_version, progress).await {
+        Ok(downloaded) => {
+            info!("Downloaded CreamLinux version: {}", downloaded.version);
+            update_creamlinux_version(&downloaded.version, downloaded.sha256)?;
         }
         Err(e) => {
             error!("Failed to download CreamLinux: {}", e);
-            return Err(format!("Failed to download CreamLinux: {}", e));
+            return Err(e.into());
         }
     }
 
@@ -58,32 +138,38 @@ pub async fn initialize_cache() -> Result<(), String> {
     Ok(())
 }
 
-// Check for updates and download new versions if available
-pub async fn check_and_update_cache() -> Result<UpdateResult, String> {
+// Check for updates and download new versions if available. `progress` is
+// forwarded to whichever unlocker(s) actually need re-downloading. Honors
+// any version pins set in config: a pinned unlocker only "updates" if the
+// pin itself changed, not whenever upstream latest moves.
+pub async fn check_and_update_cache(
+    progress: Option<ProgressSender>,
+) -> Result<UpdateResult, CacheError> {
     info!("Checking for unlocker updates...");
 
     let mut result = UpdateResult::default();
+    let (smokeapi_pin, creamlinux_pin) = load_version_pins();
 
     // Check SmokeAPI
-    let current_smokeapi = read_versions()?.smokeapi.latest;
-    match SmokeAPI::get_latest_version().await {
-        Ok(latest_version) => {
-            if current_smokeapi != latest_version {
+    let current_smokeapi = read_versions()?.smokeapi.active;
+    match SmokeAPI::resolve_target_version(smokeapi_pin.as_deref()).await {
+        Ok(target_version) => {
+            if current_smokeapi != target_version {
                 info!(
                     "SmokeAPI update available: {} -> {}",
-                    current_smokeapi, latest_version
+                    current_smokeapi, target_version
                 );
 
-                match SmokeAPI::download_to_cache().await {
-                    Ok(version) => {
-                        update_smokeapi_version(&version)?;
+                match SmokeAPI::download_to_cache(&target_version, progress.clone()).await {
+                    Ok(downloaded) => {
+                        update_smokeapi_version(&downloaded.version, downloaded.sha256)?;
                         result.smokeapi_updated = true;
-                        result.new_smokeapi_version = Some(version);
+                        result.new_smokeapi_version = Some(downloaded.version);
                         info!("SmokeAPI updated successfully");
                     }
                     Err(e) => {
                         error!("Failed to download SmokeAPI update: {}", e);
-                        return Err(format!("Failed to download SmokeAPI update: {}", e));
+                        return Err(e.into());
                     }
                 }
             } else {
@@ -96,25 +182,25 @@ pub async fn check_and_update_cache() -> Result<UpdateResult, String> {
     }
 
     // Check CreamLinux
-    let current_creamlinux = read_versions()?.creamlinux.latest;
-    match CreamLinux::get_latest_version().await {
-        Ok(latest_version) => {
-            if current_creamlinux != latest_version {
+    let current_creamlinux = read_versions()?.creamlinux.active;
+    match CreamLinux::resolve_target_version(creamlinux_pin.as_deref()).await {
+        Ok(target_version) => {
+            if current_creamlinux != target_version {
                 info!(
                     "CreamLinux update available: {} -> {}",
-                    current_creamlinux, latest_version
+                    current_creamlinux, target_version
                 );
 
-                match CreamLinux::download_to_cache().await {
-                    Ok(version) => {
-                        update_creamlinux_version(&version)?;
+                match CreamLinux::download_to_cache(&target_version, progress).await {
+                    Ok(downloaded) => {
+                        update_creamlinux_version(&downloaded.version, downloaded.sha256)?;
                         result.creamlinux_updated = true;
-                        result.new_creamlinux_version = Some(version);
+                        result.new_creamlinux_version = Some(downloaded.version);
                         info!("CreamLinux updated successfully");
                     }
                     Err(e) => {
                         error!("Failed to download CreamLinux update: {}", e);
-                        return Err(format!("Failed to download CreamLinux update: {}", e));
+                        return Err(e.into());
                     }
                 }
             } else {
@@ -132,7 +218,7 @@ pub async fn check_and_update_cache() -> Result<UpdateResult, String> {
 // Update all games that have outdated unlocker versions
 pub async fn update_outdated_games(
     games: &HashMap<String, crate::installer::Game>,
-) -> Result<GameUpdateStats, String> {
+) -> Result<GameUpdateStats, CacheError> {
     info!("Checking for outdated game installations...");
 
     let cached_versions = read_versions()?;
@@ -150,7 +236,7 @@ pub async fn update_outdated_games(
 
         // Check if SmokeAPI needs updating
         if manifest.has_smokeapi()
-            && manifest.is_smokeapi_outdated(&cached_versions.smokeapi.latest)
+            && manifest.is_smokeapi_outdated(&cached_versions.smokeapi.active)
         {
             info!(
                 "Game '{}' has outdated SmokeAPI, updating...",
@@ -161,20 +247,25 @@ pub async fn update_outdated_games(
             let api_files_str = game.api_files.join(",");
             match SmokeAPI::install_to_game(&game.path, &api_files_str).await {
                 Ok(_) => {
-                    update_game_smokeapi_version(&game.path, cached_versions.smokeapi.latest.clone())?;
+                    update_game_smokeapi_version(&game.path, cached_versions.smokeapi.active.clone())?;
                     stats.smokeapi_updated += 1;
                     info!("Updated SmokeAPI for '{}'", game.title);
                 }
                 Err(e) => {
                     error!("Failed to update SmokeAPI for '{}': {}", game.title, e);
                     stats.smokeapi_failed += 1;
+                    stats.failures.push(GameUpdateFailure {
+                        game_title: game.title.clone(),
+                        installer: "SmokeAPI",
+                        reason: e,
+                    });
                 }
             }
         }
 
         // Check if CreamLinux needs updating
         if manifest.has_creamlinux()
-            && manifest.is_creamlinux_outdated(&cached_versions.creamlinux.latest)
+            && manifest.is_creamlinux_outdated(&cached_versions.creamlinux.active)
         {
             info!(
                 "Game '{}' has outdated CreamLinux, updating...",
@@ -184,13 +275,18 @@ pub async fn update_outdated_games(
             // For CreamLinux, we need to preserve the DLC configuration
             match CreamLinux::install_to_game(&game.path, game_id).await {
                 Ok(_) => {
-                    update_game_creamlinux_version(&game.path, cached_versions.creamlinux.latest.clone())?;
+                    update_game_creamlinux_version(&game.path, cached_versions.creamlinux.active.clone())?;
                     stats.creamlinux_updated += 1;
                     info!("Updated CreamLinux for '{}'", game.title);
                 }
                 Err(e) => {
                     error!("Failed to update CreamLinux for '{}': {}", game.title, e);
                     stats.creamlinux_failed += 1;
+                    stats.failures.push(GameUpdateFailure {
+                        game_title: game.title.clone(),
+                        installer: "CreamLinux",
+                        reason: e,
+                    });
                 }
             }
         }
@@ -222,13 +318,22 @@ impl UpdateResult {
     }
 }
 
+// A single game whose in-place unlocker update failed, and why
+#[derive(Debug, Clone)]
+pub struct GameUpdateFailure {
+    pub game_title: String,
+    pub installer: &'static str,
+    pub reason: UnlockerError,
+}
+
 // Statistics about game updates
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default)]
 pub struct GameUpdateStats {
     pub smokeapi_updated: u32,
     pub smokeapi_failed: u32,
     pub creamlinux_updated: u32,
     pub creamlinux_failed: u32,
+    pub failures: Vec<GameUpdateFailure>,
 }
 
 impl GameUpdateStats {
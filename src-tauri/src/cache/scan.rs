@@ -0,0 +1,149 @@
+use crate::searcher::GameInfo;
+use log::{info, warn};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::UNIX_EPOCH;
+
+use super::storage::get_cache_dir;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct ScanCacheEntry {
+    game_info: GameInfo,
+    manifest_size: u64,
+    manifest_mtime: u64,
+    dir_mtime: u64,
+}
+
+// In-memory mirror of scan_cache.json, populated on first access. Write-through
+// front for the on-disk store, and the single lock every accessor goes
+// through - `find_installed_games` scans appmanifests across up to 8
+// concurrent tasks, so a plain read-file -> modify -> write-file cycle per
+// call would let concurrent writers race on a stale read and silently drop
+// each other's entries.
+static SCAN_CACHE: OnceLock<Mutex<HashMap<String, ScanCacheEntry>>> = OnceLock::new();
+
+fn scan_cache() -> &'static Mutex<HashMap<String, ScanCacheEntry>> {
+    SCAN_CACHE.get_or_init(|| Mutex::new(read_scan_cache_from_disk()))
+}
+
+fn get_scan_cache_path() -> Result<PathBuf, String> {
+    Ok(get_cache_dir()?.join("scan_cache.json"))
+}
+
+fn read_scan_cache_from_disk() -> HashMap<String, ScanCacheEntry> {
+    let path = match get_scan_cache_path() {
+        Ok(p) => p,
+        Err(e) => {
+            warn!("Failed to resolve scan cache path: {}", e);
+            return HashMap::new();
+        }
+    };
+
+    if !path.exists() {
+        return HashMap::new();
+    }
+
+    match fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<HashMap<String, ScanCacheEntry>>(&content).ok())
+    {
+        Some(entries) => entries,
+        None => {
+            warn!("Scan cache at {} is unreadable, starting fresh", path.display());
+            HashMap::new()
+        }
+    }
+}
+
+fn write_scan_cache(entries: &HashMap<String, ScanCacheEntry>) -> Result<(), String> {
+    let path = get_scan_cache_path()?;
+
+    let content = serde_json::to_string_pretty(entries)
+        .map_err(|e| format!("Failed to serialize scan cache: {}", e))?;
+
+    fs::write(&path, content).map_err(|e| format!("Failed to write scan cache: {}", e))?;
+
+    Ok(())
+}
+
+// Return a file's (size, mtime-as-unix-secs) fingerprint
+fn file_fingerprint(path: &Path) -> Option<(u64, u64)> {
+    let meta = fs::metadata(path).ok()?;
+    let mtime = meta
+        .modified()
+        .ok()?
+        .duration_since(UNIX_EPOCH)
+        .ok()?
+        .as_secs();
+    Some((meta.len(), mtime))
+}
+
+// Return the cached GameInfo for `appid` if its appmanifest and game
+// directory fingerprints still match what was recorded last scan
+pub fn get_cached_scanned_game(
+    appid: &str,
+    manifest_path: &Path,
+    game_dir: &Path,
+) -> Option<GameInfo> {
+    let entries = scan_cache().lock();
+    let entry = entries.get(appid)?;
+
+    let (manifest_size, manifest_mtime) = file_fingerprint(manifest_path)?;
+    let (_, dir_mtime) = file_fingerprint(game_dir)?;
+
+    if entry.manifest_size != manifest_size
+        || entry.manifest_mtime != manifest_mtime
+        || entry.dir_mtime != dir_mtime
+    {
+        return None;
+    }
+
+    Some(entry.game_info.clone())
+}
+
+// Record a freshly scanned game along with the fingerprints that justified it
+pub fn store_scanned_game(
+    appid: &str,
+    game_info: &GameInfo,
+    manifest_path: &Path,
+    game_dir: &Path,
+) -> Result<(), String> {
+    let mut entries = scan_cache().lock();
+
+    let (manifest_size, manifest_mtime) = file_fingerprint(manifest_path).unwrap_or((0, 0));
+    let (_, dir_mtime) = file_fingerprint(game_dir).unwrap_or((0, 0));
+
+    entries.insert(
+        appid.to_string(),
+        ScanCacheEntry {
+            game_info: game_info.clone(),
+            manifest_size,
+            manifest_mtime,
+            dir_mtime,
+        },
+    );
+
+    write_scan_cache(&entries)
+}
+
+// Drop entries for games whose directory no longer exists on disk
+pub fn prune_missing_games() -> Result<(), String> {
+    let mut entries = scan_cache().lock();
+    let before = entries.len();
+
+    entries.retain(|_, entry| entry.game_info.path.exists());
+
+    if entries.len() != before {
+        info!(
+            "Pruned {} stale scan cache entries",
+            before - entries.len()
+        );
+        write_scan_cache(&entries)?;
+    }
+
+    Ok(())
+}
@@ -3,6 +3,11 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 
+// How many versions a component retains on disk before the oldest gets
+// evicted. Kept low since unlocker archives are small but a user may still
+// want to roll back a release that broke a game.
+const MAX_RETAINED_VERSIONS: usize = 3;
+
 // Represents the versions.json file in the cache root
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct CacheVersions {
@@ -12,17 +17,35 @@ pub struct CacheVersions {
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct VersionInfo {
-    pub latest: String,
+    // The version games currently install from - not necessarily upstream's
+    // actual latest. A pin keeps this fixed across `update_*_version` calls,
+    // and `use_version` can move it to any retained version without
+    // re-downloading.
+    pub active: String,
+    // SHA-256 the active version's archive was verified against when it was
+    // downloaded. `None` for a version that predates this field, whose
+    // release published nothing to verify against, or that became active
+    // via `use_version` rather than a fresh download.
+    #[serde(default)]
+    pub sha256: Option<String>,
+    // Every version still present on disk, oldest first, capped at
+    // `MAX_RETAINED_VERSIONS` by `retain_version`.
+    #[serde(default)]
+    pub retained: Vec<String>,
 }
 
 impl Default for CacheVersions {
     fn default() -> Self {
         Self {
             smokeapi: VersionInfo {
-                latest: String::new(),
+                active: String::new(),
+                sha256: None,
+                retained: Vec::new(),
             },
             creamlinux: VersionInfo {
-                latest: String::new(),
+                active: String::new(),
+                sha256: None,
+                retained: Vec::new(),
             },
         }
     }
@@ -129,7 +152,7 @@ pub fn read_versions() -> Result<CacheVersions, String> {
 
     info!(
         "Read cached versions - SmokeAPI: {}, CreamLinux: {}",
-        versions.smokeapi.latest, versions.creamlinux.latest
+        versions.smokeapi.active, versions.creamlinux.active
     );
 
     Ok(versions)
@@ -148,99 +171,166 @@ pub fn write_versions(versions: &CacheVersions) -> Result<(), String> {
 
     info!(
         "Wrote versions.json - SmokeAPI: {}, CreamLinux: {}",
-        versions.smokeapi.latest, versions.creamlinux.latest
+        versions.smokeapi.active, versions.creamlinux.active
     );
 
     Ok(())
 }
 
-// Update the SmokeAPI version in versions.json and clean old version directories
-pub fn update_smokeapi_version(new_version: &str) -> Result<(), String> {
+// Record `version` as retained, evicting (and returning) the oldest
+// versions beyond `MAX_RETAINED_VERSIONS` so the caller can delete their
+// directories.
+fn retain_version(retained: &mut Vec<String>, version: &str) -> Vec<String> {
+    retained.retain(|v| v != version);
+    retained.push(version.to_string());
+
+    if retained.len() > MAX_RETAINED_VERSIONS {
+        let excess = retained.len() - MAX_RETAINED_VERSIONS;
+        retained.drain(0..excess).collect()
+    } else {
+        Vec::new()
+    }
+}
+
+fn delete_version_dir(dir: &PathBuf, component: &str, version: &str) {
+    if dir.exists() {
+        match fs::remove_dir_all(dir) {
+            Ok(_) => info!("Deleted old {} version directory: {}", component, version),
+            Err(e) => warn!(
+                "Failed to delete old {} version directory {}: {}",
+                component, version, e
+            ),
+        }
+    }
+}
+
+// Update the SmokeAPI version (and the digest it was verified against) in
+// versions.json, retaining up to `MAX_RETAINED_VERSIONS` directories on disk
+// instead of deleting everything but the active one.
+pub fn update_smokeapi_version(new_version: &str, sha256: Option<String>) -> Result<(), String> {
     let mut versions = read_versions()?;
-    let old_version = versions.smokeapi.latest.clone();
 
-    versions.smokeapi.latest = new_version.to_string();
+    versions.smokeapi.active = new_version.to_string();
+    versions.smokeapi.sha256 = sha256;
+    let evicted = retain_version(&mut versions.smokeapi.retained, new_version);
     write_versions(&versions)?;
 
-    // Delete old version directory if it exists and is different
-    if !old_version.is_empty() && old_version != new_version {
-        let old_dir = get_smokeapi_dir()?.join(&old_version);
-        if old_dir.exists() {
-            match fs::remove_dir_all(&old_dir) {
-                Ok(_) => info!("Deleted old SmokeAPI version directory: {}", old_version),
-                Err(e) => warn!(
-                    "Failed to delete old SmokeAPI version directory: {}",
-                    e
-                ),
-            }
-        }
+    let smokeapi_dir = get_smokeapi_dir()?;
+    for version in evicted {
+        delete_version_dir(&smokeapi_dir.join(&version), "SmokeAPI", &version);
     }
 
     Ok(())
 }
 
-// Update the CreamLinux version in versions.json and clean old version directories
-pub fn update_creamlinux_version(new_version: &str) -> Result<(), String> {
+// Update the CreamLinux version (and the digest it was verified against) in
+// versions.json, retaining up to `MAX_RETAINED_VERSIONS` directories on disk
+// instead of deleting everything but the active one.
+pub fn update_creamlinux_version(new_version: &str, sha256: Option<String>) -> Result<(), String> {
     let mut versions = read_versions()?;
-    let old_version = versions.creamlinux.latest.clone();
 
-    versions.creamlinux.latest = new_version.to_string();
+    versions.creamlinux.active = new_version.to_string();
+    versions.creamlinux.sha256 = sha256;
+    let evicted = retain_version(&mut versions.creamlinux.retained, new_version);
     write_versions(&versions)?;
 
-    // Delete old version directory if it exists and is different
-    if !old_version.is_empty() && old_version != new_version {
-        let old_dir = get_creamlinux_dir()?.join(&old_version);
-        if old_dir.exists() {
-            match fs::remove_dir_all(&old_dir) {
-                Ok(_) => info!("Deleted old CreamLinux version directory: {}", old_version),
-                Err(e) => warn!(
-                    "Failed to delete old CreamLinux version directory: {}",
-                    e
-                ),
-            }
-        }
+    let creamlinux_dir = get_creamlinux_dir()?;
+    for version in evicted {
+        delete_version_dir(&creamlinux_dir.join(&version), "CreamLinux", &version);
     }
 
     Ok(())
 }
 
+// Mark `version` as the active version for `unlocker` ("smokeapi" or
+// "creamlinux") without downloading anything, so a user can roll a broken
+// release back - or forward again - to any version still retained on disk.
+#[allow(dead_code)]
+pub fn use_version(unlocker: &str, version: &str) -> Result<(), String> {
+    let mut versions = read_versions()?;
+
+    let info = match unlocker {
+        "smokeapi" => &mut versions.smokeapi,
+        "creamlinux" => &mut versions.creamlinux,
+        other => return Err(format!("Unknown unlocker: {}", other)),
+    };
+
+    if !info.retained.iter().any(|v| v == version) {
+        return Err(format!(
+            "{} version {} is not cached; download it before switching to it",
+            unlocker, version
+        ));
+    }
+
+    info.active = version.to_string();
+    // We didn't just verify this archive's digest, so don't claim we did.
+    info.sha256 = None;
+
+    write_versions(&versions)
+}
+
+// Every version of `unlocker` ("smokeapi" or "creamlinux") present on disk,
+// sorted by name. Reads the cache directory directly rather than trusting
+// `retained` alone, so it still reflects reality if a version directory was
+// added or removed outside this program.
+#[allow(dead_code)]
+pub fn list_cached_versions(unlocker: &str) -> Result<Vec<String>, String> {
+    let dir = match unlocker {
+        "smokeapi" => get_smokeapi_dir()?,
+        "creamlinux" => get_creamlinux_dir()?,
+        other => return Err(format!("Unknown unlocker: {}", other)),
+    };
+
+    let entries = fs::read_dir(&dir)
+        .map_err(|e| format!("Failed to read {} cache directory: {}", unlocker, e))?;
+
+    let mut versions: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect();
+
+    versions.sort();
+    Ok(versions)
+}
+
 // Check if the cache is initialized (has both unlockers cached)
 pub fn is_cache_initialized() -> Result<bool, String> {
     let versions = read_versions()?;
-    Ok(!versions.smokeapi.latest.is_empty() && !versions.creamlinux.latest.is_empty())
+    Ok(!versions.smokeapi.active.is_empty() && !versions.creamlinux.active.is_empty())
 }
 
-// Get the SmokeAPI DLL path for the latest cached version
+// Get the SmokeAPI DLL path for the active cached version
 #[allow(dead_code)]
 pub fn get_smokeapi_dll_path() -> Result<PathBuf, String> {
     let versions = read_versions()?;
-    if versions.smokeapi.latest.is_empty() {
+    if versions.smokeapi.active.is_empty() {
         return Err("SmokeAPI is not cached".to_string());
     }
 
-    let version_dir = get_smokeapi_version_dir(&versions.smokeapi.latest)?;
+    let version_dir = get_smokeapi_version_dir(&versions.smokeapi.active)?;
     Ok(version_dir.join("SmokeAPI.dll"))
 }
 
-// Get the CreamLinux files directory path for the latest cached version
+// Get the CreamLinux files directory path for the active cached version
 #[allow(dead_code)]
 pub fn get_creamlinux_files_dir() -> Result<PathBuf, String> {
     let versions = read_versions()?;
-    if versions.creamlinux.latest.is_empty() {
+    if versions.creamlinux.active.is_empty() {
         return Err("CreamLinux is not cached".to_string());
     }
 
-    get_creamlinux_version_dir(&versions.creamlinux.latest)
+    get_creamlinux_version_dir(&versions.creamlinux.active)
 }
 
-// List all SmokeAPI DLL files in the cached version directory
+// List all SmokeAPI DLL files in the active cached version directory
 pub fn list_smokeapi_dlls() -> Result<Vec<PathBuf>, String> {
     let versions = read_versions()?;
-    if versions.smokeapi.latest.is_empty() {
+    if versions.smokeapi.active.is_empty() {
         return Ok(Vec::new());
     }
 
-    let version_dir = get_smokeapi_version_dir(&versions.smokeapi.latest)?;
+    let version_dir = get_smokeapi_version_dir(&versions.smokeapi.active)?;
 
     if !version_dir.exists() {
         return Ok(Vec::new());
@@ -262,14 +352,14 @@ pub fn list_smokeapi_dlls() -> Result<Vec<PathBuf>, String> {
     Ok(dlls)
 }
 
-// List all CreamLinux files in the cached version directory
+// List all CreamLinux files in the active cached version directory
 pub fn list_creamlinux_files() -> Result<Vec<PathBuf>, String> {
     let versions = read_versions()?;
-    if versions.creamlinux.latest.is_empty() {
+    if versions.creamlinux.active.is_empty() {
         return Ok(Vec::new());
     }
 
-    let version_dir = get_creamlinux_version_dir(&versions.creamlinux.latest)?;
+    let version_dir = get_creamlinux_version_dir(&versions.creamlinux.active)?;
 
     if !version_dir.exists() {
         return Ok(Vec::new());
@@ -289,4 +379,4 @@ pub fn list_creamlinux_files() -> Result<Vec<PathBuf>, String> {
     }
 
     Ok(files)
-}
\ No newline at end of file
+}
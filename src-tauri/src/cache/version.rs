@@ -1,8 +1,46 @@
-use log::{info};
+use log::info;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::fs;
 use std::path::Path;
 
+// Result of comparing an installed version against the cache's active
+// version. Distinguishing `Newer` from `UpdateAvailable` matters because a
+// manually-installed build ahead of what's cached should never be silently
+// "updated" back down to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionComparison {
+    UpToDate,
+    UpdateAvailable,
+    Newer,
+}
+
+// Strip a leading `v`/`V` so `v1.2.0` and `1.2.0` parse as the same semver.
+fn normalize(version: &str) -> &str {
+    version.trim_start_matches(['v', 'V'])
+}
+
+// Compare `installed` against `latest`, preferring semantic-version
+// comparison over string equality so `v1.2.0` isn't flagged outdated
+// against `1.2.0`, and a newer manual install isn't confused with one that
+// needs updating. Falls back to exact-string comparison when either side
+// isn't valid semver (GitHub tags aren't always clean), so behavior never
+// regresses for a release that doesn't follow semver.
+fn compare_versions(installed: &str, latest: &str) -> VersionComparison {
+    match (
+        semver::Version::parse(normalize(installed)),
+        semver::Version::parse(normalize(latest)),
+    ) {
+        (Ok(installed), Ok(latest)) => match installed.cmp(&latest) {
+            Ordering::Less => VersionComparison::UpdateAvailable,
+            Ordering::Equal => VersionComparison::UpToDate,
+            Ordering::Greater => VersionComparison::Newer,
+        },
+        _ if installed == latest => VersionComparison::UpToDate,
+        _ => VersionComparison::UpdateAvailable,
+    }
+}
+
 // Represents the version manifest stored in each game directory
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct GameManifest {
@@ -38,20 +76,36 @@ impl GameManifest {
         self.creamlinux_version.is_some()
     }
 
+    // Semver-aware comparison of the installed SmokeAPI version against
+    // `latest_version`, or `None` if SmokeAPI isn't installed at all.
+    pub fn compare_smokeapi_version(&self, latest_version: &str) -> Option<VersionComparison> {
+        self.smokeapi_version
+            .as_deref()
+            .map(|version| compare_versions(version, latest_version))
+    }
+
+    // Semver-aware comparison of the installed CreamLinux version against
+    // `latest_version`, or `None` if CreamLinux isn't installed at all.
+    pub fn compare_creamlinux_version(&self, latest_version: &str) -> Option<VersionComparison> {
+        self.creamlinux_version
+            .as_deref()
+            .map(|version| compare_versions(version, latest_version))
+    }
+
     // Check if SmokeAPI version is outdated
     pub fn is_smokeapi_outdated(&self, latest_version: &str) -> bool {
-        match &self.smokeapi_version {
-            Some(version) => version != latest_version,
-            None => false,
-        }
+        matches!(
+            self.compare_smokeapi_version(latest_version),
+            Some(VersionComparison::UpdateAvailable)
+        )
     }
 
     // Check if CreamLinux version is outdated
     pub fn is_creamlinux_outdated(&self, latest_version: &str) -> bool {
-        match &self.creamlinux_version {
-            Some(version) => version != latest_version,
-            None => false,
-        }
+        matches!(
+            self.compare_creamlinux_version(latest_version),
+            Some(VersionComparison::UpdateAvailable)
+        )
     }
 }
 
@@ -174,4 +228,33 @@ mod tests {
         assert!(manifest.is_creamlinux_outdated("v2.0.0"));
         assert!(!manifest.is_creamlinux_outdated("v1.5.0"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_compare_versions_treats_v_prefix_as_equivalent() {
+        assert_eq!(compare_versions("v1.2.0", "1.2.0"), VersionComparison::UpToDate);
+    }
+
+    #[test]
+    fn test_compare_versions_detects_newer_installed() {
+        assert_eq!(compare_versions("v2.0.0", "v1.5.0"), VersionComparison::Newer);
+    }
+
+    #[test]
+    fn test_compare_versions_falls_back_to_string_equality_for_non_semver() {
+        assert_eq!(compare_versions("nightly", "nightly"), VersionComparison::UpToDate);
+        assert_eq!(
+            compare_versions("nightly-2024", "nightly-2025"),
+            VersionComparison::UpdateAvailable
+        );
+    }
+
+    #[test]
+    fn test_manifest_not_outdated_when_ahead_of_cache() {
+        let manifest = GameManifest::with_smokeapi("v3.0.0".to_string());
+        assert!(!manifest.is_smokeapi_outdated("v2.0.0"));
+        assert_eq!(
+            manifest.compare_smokeapi_version("v2.0.0"),
+            Some(VersionComparison::Newer)
+        );
+    }
+}
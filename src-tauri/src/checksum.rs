@@ -0,0 +1,153 @@
+// SHA-256 helpers for verifying a downloaded unlocker archive against a
+// checksums file published alongside it. Not every release publishes one,
+// so a missing entry degrades to a warning rather than a hard failure —
+// see callers in `unlockers::smokeapi` and `unlockers::creamlinux`.
+
+use log::warn;
+use sha2::{Digest, Sha256};
+
+// Compute the lowercase hex SHA-256 digest of `data`
+pub fn sha256_hex(data: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    format!("{:x}", hasher.finalize())
+}
+
+// Look up the expected hash for `file_name` inside a `sha256sum`-style
+// checksums file: one `<hex digest>  <filename>` entry per line, optionally
+// prefixed with `*` to mark binary mode
+pub fn find_expected_hash(checksums: &str, file_name: &str) -> Option<String> {
+    checksums.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        let hash = parts.next()?;
+        let name = parts.next()?.trim_start_matches('*');
+        if name == file_name || name.ends_with(&format!("/{}", file_name)) {
+            Some(hash.to_lowercase())
+        } else {
+            None
+        }
+    })
+}
+
+// Verify `data` against a checksums file's entry for `file_name`:
+// - `Some(true)` — an entry was found and matched
+// - `Some(false)` — an entry was found and did NOT match
+// - `None` — no entry for `file_name`, so there's nothing to verify against
+pub fn verify_against_checksums(data: &[u8], checksums: &str, file_name: &str) -> Option<bool> {
+    verify_digest_against_checksums(&sha256_hex(data), checksums, file_name)
+}
+
+// Like `verify_against_checksums`, but takes an already-computed digest
+// instead of the raw bytes - for callers that hashed incrementally while
+// streaming a download and never held the whole file in memory.
+pub fn verify_digest_against_checksums(actual: &str, checksums: &str, file_name: &str) -> Option<bool> {
+    find_expected_hash(checksums, file_name).map(|expected| {
+        let matches = actual.eq_ignore_ascii_case(&expected);
+
+        if !matches {
+            warn!(
+                "Checksum mismatch for {}: expected {}, got {}",
+                file_name, expected, actual
+            );
+        }
+
+        matches
+    })
+}
+
+// GitHub's release API publishes a `digest` field on each asset itself
+// (`"sha256:<hex>"`), so a release that never bothered publishing a separate
+// checksums file can often still be verified without one.
+pub fn find_asset_digest_sha256(assets: &[serde_json::Value], file_name: &str) -> Option<String> {
+    let digest = assets
+        .iter()
+        .find(|asset| asset.get("name").and_then(|v| v.as_str()) == Some(file_name))?
+        .get("digest")?
+        .as_str()?;
+
+    digest.strip_prefix("sha256:").map(str::to_lowercase)
+}
+
+// Find a GitHub release asset that looks like a checksums file (its name
+// contains "sha256" or "checksum"), returning its download URL
+pub fn find_checksums_asset_url(assets: &[serde_json::Value]) -> Option<String> {
+    assets.iter().find_map(|asset| {
+        let name = asset.get("name")?.as_str()?.to_lowercase();
+        if name.contains("sha256") || name.contains("checksum") {
+            asset
+                .get("browser_download_url")?
+                .as_str()
+                .map(str::to_string)
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha256_hex_known_value() {
+        assert_eq!(
+            sha256_hex(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn test_find_expected_hash_matches_by_name() {
+        let checksums = "deadbeef  smokeapi.zip\ncafebabe  other.zip\n";
+        assert_eq!(
+            find_expected_hash(checksums, "smokeapi.zip"),
+            Some("deadbeef".to_string())
+        );
+    }
+
+    #[test]
+    fn test_find_expected_hash_missing_entry() {
+        let checksums = "deadbeef  other.zip\n";
+        assert_eq!(find_expected_hash(checksums, "smokeapi.zip"), None);
+    }
+
+    #[test]
+    fn test_verify_against_checksums_detects_mismatch() {
+        let checksums = format!("{}  file.zip\n", sha256_hex(b"expected"));
+        assert_eq!(
+            verify_against_checksums(b"not expected", &checksums, "file.zip"),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn test_find_asset_digest_sha256_parses_prefixed_digest() {
+        let assets = serde_json::json!([
+            {"name": "smokeapi.zip", "digest": "sha256:DEADBEEF"},
+            {"name": "other.zip", "digest": "sha256:cafebabe"},
+        ]);
+        assert_eq!(
+            find_asset_digest_sha256(assets.as_array().unwrap(), "smokeapi.zip"),
+            Some("deadbeef".to_string())
+        );
+    }
+
+    #[test]
+    fn test_find_asset_digest_sha256_missing_field() {
+        let assets = serde_json::json!([{"name": "smokeapi.zip"}]);
+        assert_eq!(
+            find_asset_digest_sha256(assets.as_array().unwrap(), "smokeapi.zip"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_verify_against_checksums_confirms_match() {
+        let data = b"some archive bytes";
+        let checksums = format!("{}  file.zip\n", sha256_hex(data));
+        assert_eq!(
+            verify_against_checksums(data, &checksums, "file.zip"),
+            Some(true)
+        );
+    }
+}
@@ -0,0 +1,140 @@
+// Lists the releases available for each unlocker component, mirroring the
+// versioned-component manifest shape used by launchers like
+// anime-game-launcher: a flat, cacheable list the UI can present as a
+// version picker, with one entry flagged `recommended` (currently: latest).
+//
+// This sits above `Unlocker::resolve_target_version`, which only ever
+// resolves a single pinned-or-latest tag - this module is what lets the UI
+// discover which tags exist in the first place, before a pin is chosen.
+
+use crate::unlockers::creamlinux::CREAMLINUX_REPO;
+use crate::unlockers::smokeapi::SMOKEAPI_REPO;
+use log::{info, warn};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ComponentVersion {
+    pub family: &'static str,
+    pub tag: String,
+    pub title: String,
+    pub download_url: String,
+    pub recommended: bool,
+}
+
+// In-memory cache of each family's release list, so re-opening the version
+// picker doesn't re-hit the GitHub API every time
+static COMPONENT_CACHE: OnceLock<Mutex<HashMap<&'static str, Vec<ComponentVersion>>>> =
+    OnceLock::new();
+
+fn cache() -> &'static Mutex<HashMap<&'static str, Vec<ComponentVersion>>> {
+    COMPONENT_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn repo_for_family(family: &str) -> Result<&'static str, String> {
+    match family {
+        "creamlinux" => Ok(CREAMLINUX_REPO),
+        "smokeapi" => Ok(SMOKEAPI_REPO),
+        other => Err(format!("Unknown component family: {}", other)),
+    }
+}
+
+// Fetch every non-draft, non-prerelease release for a component family from
+// GitHub, newest first, with the newest flagged as recommended
+async fn fetch_versions(family: &'static str, repo: &str) -> Result<Vec<ComponentVersion>, String> {
+    info!("Fetching available {} releases from {}", family, repo);
+
+    let client = reqwest::Client::new();
+    let url = format!("https://api.github.com/repos/{}/releases", repo);
+
+    let response = client
+        .get(&url)
+        .header("User-Agent", "CreamLinux-Installer")
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch {} releases: {}", family, e))?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "Failed to fetch {} releases: HTTP {}",
+            family,
+            response.status()
+        ));
+    }
+
+    let releases: Vec<serde_json::Value> = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse {} releases: {}", family, e))?;
+
+    let mut versions = Vec::new();
+    for release in releases.iter() {
+        let is_draft = release.get("draft").and_then(|v| v.as_bool()).unwrap_or(false);
+        let is_prerelease = release
+            .get("prerelease")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        if is_draft || is_prerelease {
+            continue;
+        }
+
+        let Some(tag) = release.get("tag_name").and_then(|v| v.as_str()) else {
+            warn!("Skipping {} release with no tag_name", family);
+            continue;
+        };
+
+        let title = release
+            .get("name")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .unwrap_or(tag)
+            .to_string();
+
+        let download_url = release
+            .get("assets")
+            .and_then(|a| a.as_array())
+            .and_then(|assets| assets.first())
+            .and_then(|asset| asset.get("browser_download_url"))
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string();
+
+        versions.push(ComponentVersion {
+            family,
+            tag: tag.to_string(),
+            title,
+            download_url,
+            // The first entry that actually makes it into `versions` - i.e.
+            // the newest non-draft, non-prerelease release - not the first
+            // entry in the unfiltered API response, which may itself have
+            // been skipped above.
+            recommended: versions.is_empty(),
+        });
+    }
+
+    info!("Found {} {} releases", versions.len(), family);
+    Ok(versions)
+}
+
+// List the available versions for a component family ("creamlinux" or
+// "smokeapi"), serving the cached list if one is available
+pub async fn list_component_versions(family: &str) -> Result<Vec<ComponentVersion>, String> {
+    let repo = repo_for_family(family)?;
+    let family: &'static str = match family {
+        "creamlinux" => "creamlinux",
+        "smokeapi" => "smokeapi",
+        _ => unreachable!("validated by repo_for_family"),
+    };
+
+    if let Some(cached) = cache().lock().get(family) {
+        return Ok(cached.clone());
+    }
+
+    let versions = fetch_versions(family, repo).await?;
+    cache().lock().insert(family, versions.clone());
+    Ok(versions)
+}
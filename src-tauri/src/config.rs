@@ -1,94 +1,304 @@
+use log::info;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::fs;
-use std::path::PathBuf;
-use log::info;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+// A config file format recognized by its `config.<ext>` extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConfigFormat {
+    Json,
+    Toml,
+    Ron,
+}
+
+impl ConfigFormat {
+    const ALL: [ConfigFormat; 3] = [ConfigFormat::Json, ConfigFormat::Toml, ConfigFormat::Ron];
+
+    fn extension(&self) -> &'static str {
+        match self {
+            ConfigFormat::Json => "json",
+            ConfigFormat::Toml => "toml",
+            ConfigFormat::Ron => "ron",
+        }
+    }
+}
+
+// Structured error type for the config layer, mirroring `InstallerError`
+// so a config failure can eventually be surfaced to the frontend with more
+// than just an English sentence.
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("unrecognized config file extension: .{0}")]
+    UnknownExtension(String),
+
+    #[error("config file has no extension")]
+    MissingExtension,
+
+    #[error("failed to parse config: {0}")]
+    Parse(String),
+
+    #[error("failed to determine config directory: {0}")]
+    BaseDirectories(String),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+// Tauri commands still return `Result<_, String>`, so callers can propagate
+// a `ConfigError` with `?` without an explicit `.map_err`.
+impl From<ConfigError> for String {
+    fn from(err: ConfigError) -> Self {
+        err.to_string()
+    }
+}
+
+// Deserialize a config file's contents according to its detected format
+fn parse_config_str(content: &str, format: ConfigFormat) -> Result<Value, ConfigError> {
+    match format {
+        ConfigFormat::Json => {
+            serde_json::from_str(content).map_err(|e| ConfigError::Parse(e.to_string()))
+        }
+        ConfigFormat::Toml => {
+            toml::from_str(content).map_err(|e| ConfigError::Parse(e.to_string()))
+        }
+        ConfigFormat::Ron => {
+            ron::from_str(content).map_err(|e| ConfigError::Parse(e.to_string()))
+        }
+    }
+}
+
+// Serialize a config in the given format
+fn serialize_config(config: &Config, format: ConfigFormat) -> Result<String, ConfigError> {
+    match format {
+        ConfigFormat::Json => {
+            serde_json::to_string_pretty(config).map_err(|e| ConfigError::Parse(e.to_string()))
+        }
+        ConfigFormat::Toml => {
+            toml::to_string_pretty(config).map_err(|e| ConfigError::Parse(e.to_string()))
+        }
+        ConfigFormat::Ron => ron::ser::to_string_pretty(config, ron::ser::PrettyConfig::default())
+            .map_err(|e| ConfigError::Parse(e.to_string())),
+    }
+}
+
+// Find the config file actually present in `config_dir`, trying each known
+// extension. If none of them exist but a `config.*` file does anyway,
+// report its extension so the caller can give a clearer error than "missing".
+fn find_config_file(config_dir: &Path) -> Result<Option<(PathBuf, ConfigFormat)>, ConfigError> {
+    for format in ConfigFormat::ALL {
+        let path = config_dir.join(format!("config.{}", format.extension()));
+        if path.exists() {
+            return Ok(Some((path, format)));
+        }
+    }
+
+    if let Ok(entries) = fs::read_dir(config_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.file_stem().and_then(|s| s.to_str()) == Some("config") {
+                return Err(match path.extension().and_then(|e| e.to_str()) {
+                    Some(ext) => ConfigError::UnknownExtension(ext.to_string()),
+                    None => ConfigError::MissingExtension,
+                });
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+// Bump this whenever `Config`'s schema changes, and add the matching
+// migration to `MIGRATIONS` below
+pub const CURRENT_CONFIG_VERSION: u32 = 2;
 
 // User configuration structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
+    // Schema version this config was last saved as; used to migrate older
+    // config files forward instead of failing to deserialize them
+    #[serde(default)]
+    pub version: u32,
     // Whether to show the disclaimer on startup
     pub show_disclaimer: bool,
+    // Pin SmokeAPI to a specific release tag instead of always tracking
+    // latest. `None` means "follow latest".
+    #[serde(default)]
+    pub smokeapi_version_pin: Option<String>,
+    // Same as `smokeapi_version_pin`, for CreamLinux
+    #[serde(default)]
+    pub creamlinux_version_pin: Option<String>,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
+            version: CURRENT_CONFIG_VERSION,
             show_disclaimer: true,
+            smokeapi_version_pin: None,
+            creamlinux_version_pin: None,
         }
     }
 }
 
-// Get the config directory path (~/.config/creamlinux)
-fn get_config_dir() -> Result<PathBuf, String> {
-    let home = std::env::var("HOME")
-        .map_err(|_| "Failed to get HOME directory".to_string())?;
-    
-    let config_dir = PathBuf::from(home).join(".config").join("creamlinux");
-    Ok(config_dir)
+// Ordered chain of migrations: entry `i` upgrades a raw config from version
+// `i` to version `i + 1`. A missing `version` key is treated as 0.
+const MIGRATIONS: &[fn(&mut Value)] = &[migrate_v0_to_v1, migrate_v1_to_v2];
+
+// v0 configs predate the `show_disclaimer` field; default it in if absent
+fn migrate_v0_to_v1(value: &mut Value) {
+    if let Value::Object(map) = value {
+        map.entry("show_disclaimer").or_insert(Value::Bool(true));
+    }
 }
 
-// Get the config file path
-fn get_config_path() -> Result<PathBuf, String> {
-    let config_dir = get_config_dir()?;
-    Ok(config_dir.join("config.json"))
+// v1 configs predate per-unlocker version pins; default both to "follow latest"
+fn migrate_v1_to_v2(value: &mut Value) {
+    if let Value::Object(map) = value {
+        map.entry("smokeapi_version_pin").or_insert(Value::Null);
+        map.entry("creamlinux_version_pin").or_insert(Value::Null);
+    }
+}
+
+// Run every migration needed to bring `value` up to
+// `CURRENT_CONFIG_VERSION`, stamping the final version back onto it.
+// Returns whether any migration actually ran.
+fn migrate(value: &mut Value) -> bool {
+    let mut version = value
+        .get("version")
+        .and_then(Value::as_u64)
+        .unwrap_or(0) as usize;
+
+    let migrated = version < MIGRATIONS.len();
+
+    while version < MIGRATIONS.len() {
+        info!("Migrating config from version {} to {}", version, version + 1);
+        MIGRATIONS[version](value);
+        version += 1;
+    }
+
+    if let Value::Object(map) = value {
+        map.insert("version".to_string(), Value::from(version as u64));
+    }
+
+    migrated
+}
+
+// Environment variable that overrides the config directory outright,
+// bypassing both the `--config-dir` CLI flag and the XDG lookup below
+const CONFIG_DIR_ENV_VAR: &str = "CREAMLINUX_CONFIG_DIR";
+
+// CLI flag accepted as `--config-dir <path>` or `--config-dir=<path>`
+const CONFIG_DIR_FLAG: &str = "--config-dir";
+
+// Look for `--config-dir <path>` / `--config-dir=<path>` among the process's
+// own arguments
+fn config_dir_from_args() -> Option<PathBuf> {
+    let eq_prefix = format!("{}=", CONFIG_DIR_FLAG);
+    let mut args = std::env::args();
+    while let Some(arg) = args.next() {
+        if let Some(value) = arg.strip_prefix(&eq_prefix) {
+            return Some(PathBuf::from(value));
+        }
+        if arg == CONFIG_DIR_FLAG {
+            return args.next().map(PathBuf::from);
+        }
+    }
+    None
+}
+
+// Get the config directory path, honoring (in priority order) the
+// `CREAMLINUX_CONFIG_DIR` env var, the `--config-dir` CLI flag, and
+// finally falling back to the XDG Base Directory spec (`$XDG_CONFIG_HOME`,
+// defaulting to `~/.config/creamlinux`)
+pub(crate) fn get_config_dir() -> Result<PathBuf, ConfigError> {
+    if let Ok(dir) = std::env::var(CONFIG_DIR_ENV_VAR) {
+        return Ok(PathBuf::from(dir));
+    }
+
+    if let Some(dir) = config_dir_from_args() {
+        return Ok(dir);
+    }
+
+    let xdg_dirs = xdg::BaseDirectories::with_prefix("creamlinux")
+        .map_err(|e| ConfigError::BaseDirectories(e.to_string()))?;
+
+    Ok(xdg_dirs.get_config_home())
 }
 
 // Ensure the config directory exists
-fn ensure_config_dir() -> Result<(), String> {
+fn ensure_config_dir() -> Result<(), ConfigError> {
     let config_dir = get_config_dir()?;
-    
+
     if !config_dir.exists() {
-        fs::create_dir_all(&config_dir)
-            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+        fs::create_dir_all(&config_dir)?;
         info!("Created config directory at {:?}", config_dir);
     }
-    
+
     Ok(())
 }
 
-// Load configuration from disk
-pub fn load_config() -> Result<Config, String> {
+// Load configuration from disk, probing for config.json, config.toml and
+// config.ron in turn and parsing whichever one is actually present
+pub fn load_config() -> Result<Config, ConfigError> {
     ensure_config_dir()?;
-    
-    let config_path = get_config_path()?;
-    
-    // If config file doesn't exist, create default config
-    if !config_path.exists() {
-        let default_config = Config::default();
-        save_config(&default_config)?;
-        info!("Created default config file at {:?}", config_path);
-        return Ok(default_config);
-    }
-    
-    // Read and parse config file
-    let config_str = fs::read_to_string(&config_path)
-        .map_err(|e| format!("Failed to read config file: {}", e))?;
-    
-    let config: Config = serde_json::from_str(&config_str)
-        .map_err(|e| format!("Failed to parse config file: {}", e))?;
-    
+
+    let config_dir = get_config_dir()?;
+
+    // If no recognized config file exists, create a default JSON one
+    let (config_path, format) = match find_config_file(&config_dir)? {
+        Some(found) => found,
+        None => {
+            let default_config = Config::default();
+            save_config(&default_config)?;
+            info!("Created default config file in {:?}", config_dir);
+            return Ok(default_config);
+        }
+    };
+
+    // Read the raw config as an untyped value first so older schemas can be
+    // migrated forward instead of failing to deserialize straight into `Config`
+    let config_str = fs::read_to_string(&config_path)?;
+
+    let mut value = parse_config_str(&config_str, format)?;
+
+    let migrated = migrate(&mut value);
+
+    let config: Config = serde_json::from_value(value)
+        .map_err(|e| ConfigError::Parse(e.to_string()))?;
+
+    if migrated {
+        save_config(&config)?;
+        info!("Migrated config file at {:?} to version {}", config_path, CURRENT_CONFIG_VERSION);
+    }
+
     info!("Loaded config from {:?}", config_path);
     Ok(config)
 }
 
-// Save configuration to disk
-pub fn save_config(config: &Config) -> Result<(), String> {
+// Save configuration to disk, preserving whichever format the current
+// config file is already in (defaulting to JSON for a brand new one)
+pub fn save_config(config: &Config) -> Result<(), ConfigError> {
     ensure_config_dir()?;
-    
-    let config_path = get_config_path()?;
-    
-    let config_str = serde_json::to_string_pretty(config)
-        .map_err(|e| format!("Failed to serialize config: {}", e))?;
-    
-    fs::write(&config_path, config_str)
-        .map_err(|e| format!("Failed to write config file: {}", e))?;
-    
+
+    let config_dir = get_config_dir()?;
+
+    let (config_path, format) = match find_config_file(&config_dir)? {
+        Some(found) => found,
+        None => (config_dir.join("config.json"), ConfigFormat::Json),
+    };
+
+    let config_str = serialize_config(config, format)?;
+
+    fs::write(&config_path, config_str)?;
+
     info!("Saved config to {:?}", config_path);
     Ok(())
 }
 
 // Update a specific config value
-pub fn update_config<F>(updater: F) -> Result<Config, String>
+pub fn update_config<F>(updater: F) -> Result<Config, ConfigError>
 where
     F: FnOnce(&mut Config),
 {
@@ -115,4 +325,86 @@ mod tests {
         let parsed: Config = serde_json::from_str(&json).unwrap();
         assert_eq!(config.show_disclaimer, parsed.show_disclaimer);
     }
+
+    #[test]
+    fn test_migrate_v0_config_adds_show_disclaimer() {
+        // A v0 config predates both `version` and `show_disclaimer`
+        let mut value: Value = serde_json::from_str("{}").unwrap();
+        assert!(migrate(&mut value));
+
+        assert_eq!(value["version"], Value::from(CURRENT_CONFIG_VERSION as u64));
+        assert_eq!(value["show_disclaimer"], Value::Bool(true));
+    }
+
+    #[test]
+    fn test_migrate_preserves_existing_value() {
+        let mut value: Value = serde_json::from_str(r#"{"show_disclaimer": false}"#).unwrap();
+        migrate(&mut value);
+
+        assert_eq!(value["show_disclaimer"], Value::Bool(false));
+    }
+
+    #[test]
+    fn test_migrate_v1_config_adds_version_pins() {
+        let mut value = serde_json::json!({
+            "version": 1,
+            "show_disclaimer": true,
+        });
+
+        assert!(migrate(&mut value));
+
+        assert_eq!(value["version"], Value::from(CURRENT_CONFIG_VERSION as u64));
+        assert_eq!(value["smokeapi_version_pin"], Value::Null);
+        assert_eq!(value["creamlinux_version_pin"], Value::Null);
+    }
+
+    #[test]
+    fn test_migrate_current_version_is_noop() {
+        let mut value = serde_json::json!({
+            "version": CURRENT_CONFIG_VERSION,
+            "show_disclaimer": false,
+        });
+
+        assert!(!migrate(&mut value));
+        assert_eq!(value["show_disclaimer"], Value::Bool(false));
+    }
+
+    #[test]
+    fn test_parse_config_str_toml() {
+        let toml_str = "version = 1\nshow_disclaimer = true\n";
+        let value = parse_config_str(toml_str, ConfigFormat::Toml).unwrap();
+        assert_eq!(value["show_disclaimer"], Value::Bool(true));
+    }
+
+    #[test]
+    fn test_parse_config_str_ron() {
+        let ron_str = "(version: 1, show_disclaimer: true)";
+        let value = parse_config_str(ron_str, ConfigFormat::Ron).unwrap();
+        assert_eq!(value["show_disclaimer"], Value::Bool(true));
+    }
+
+    #[test]
+    fn test_find_config_file_reports_unknown_extension() {
+        let dir = std::env::temp_dir().join(format!("creamlinux_config_test_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("config.yaml"), "show_disclaimer: true").unwrap();
+
+        let result = find_config_file(&dir);
+        assert!(matches!(
+            result,
+            Err(ConfigError::UnknownExtension(ext)) if ext == "yaml"
+        ));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_get_config_dir_honors_env_override() {
+        let dir = std::env::temp_dir().join(format!("creamlinux_config_env_test_{}", std::process::id()));
+        std::env::set_var(CONFIG_DIR_ENV_VAR, &dir);
+
+        assert_eq!(get_config_dir().unwrap(), dir);
+
+        std::env::remove_var(CONFIG_DIR_ENV_VAR);
+    }
 }
\ No newline at end of file
@@ -1,16 +1,55 @@
-use log::{error, info};
+use log::{error, info, warn};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::Path;
 use tauri::Manager;
 
+// Fetch the full DLC catalog for a game directly from Steam, independent of
+// any existing cream_api.ini. Delegates to `installer::fetch_dlc_details`,
+// which already implements the rate-limit-safe appdetails lookup (429
+// backoff, skipping DLCs that come back `success: false`); every DLC is
+// returned enabled by default so the UI can present the complete catalog
+// before cream_api.ini exists.
+pub async fn fetch_dlcs_from_steam(
+    game_id: &str,
+    app_handle: &tauri::AppHandle,
+    force_refresh: bool,
+) -> Result<Vec<DlcInfoWithState>, String> {
+    use crate::AppState;
+
+    let game_path = {
+        let state = app_handle.state::<AppState>();
+        let games = state.games.lock();
+        games
+            .get(game_id)
+            .map(|g| g.path.clone())
+            .ok_or_else(|| format!("Game with ID {} not found", game_id))?
+    };
+
+    let dlcs = crate::installer::fetch_dlc_details(game_id, &game_path, force_refresh)
+        .await
+        .map_err(|e| format!("Failed to fetch DLC details from Steam: {}", e))?;
+
+    Ok(dlcs
+        .into_iter()
+        .map(|dlc| DlcInfoWithState {
+            appid: dlc.appid,
+            name: dlc.name,
+            enabled: true,
+            owned: dlc.owned,
+        })
+        .collect())
+}
+
 // More detailed DLC information with enabled state
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct DlcInfoWithState {
     pub appid: String,
     pub name: String,
     pub enabled: bool,
+    #[serde(default)]
+    pub owned: bool,
 }
 
 // Parse the cream_api.ini file to extract both enabled and disabled DLCs
@@ -120,6 +159,7 @@ pub fn get_all_dlcs(game_path: &str) -> Result<Vec<DlcInfoWithState>, String> {
                     appid: appid.to_string(),
                     name: name.to_string().trim_matches('"').to_string(),
                     enabled: !is_commented,
+                    owned: crate::searcher::is_app_installed(Path::new(game_path), appid),
                 });
             }
         }
@@ -135,33 +175,27 @@ pub fn get_all_dlcs(game_path: &str) -> Result<Vec<DlcInfoWithState>, String> {
     Ok(all_dlcs)
 }
 
-// Update the cream_api.ini file with the user's DLC selections
-pub fn update_dlc_configuration(
-    game_path: &str,
-    dlcs: Vec<DlcInfoWithState>,
-) -> Result<(), String> {
-    info!("Updating DLC configuration for {}", game_path);
+// Restore cream_api.ini from the `.bak` backup left by the last atomic write,
+// for one-click recovery if a DLC configuration write was ever interrupted
+pub fn restore_cream_api_backup(game_path: &str) -> Result<(), String> {
+    info!("Restoring cream_api.ini backup for {}", game_path);
 
     let cream_api_path = Path::new(game_path).join("cream_api.ini");
-    if !cream_api_path.exists() {
-        return Err(format!(
-            "cream_api.ini not found at {}",
-            cream_api_path.display()
-        ));
-    }
 
-    // Read the current file contents
-    let current_contents = match fs::read_to_string(&cream_api_path) {
-        Ok(c) => c,
-        Err(e) => return Err(format!("Failed to read cream_api.ini: {}", e)),
-    };
+    crate::installer::restore_backup(&cream_api_path)
+        .map_err(|e| format!("Failed to restore cream_api.ini backup: {}", e))?;
 
-    // Create a mapping of DLC appid to its state for easy lookup
-    let dlc_states: HashMap<String, (bool, String)> = dlcs
-        .iter()
-        .map(|dlc| (dlc.appid.clone(), (dlc.enabled, dlc.name.clone())))
-        .collect();
+    info!("Successfully restored cream_api.ini from backup");
+    Ok(())
+}
 
+// Rebuild cream_api.ini's lines for a new set of DLC selections, preserving
+// everything that isn't a DLC toggle. Shared by `update_dlc_configuration`
+// and `preview_dlc_configuration` so the two can't drift from each other.
+fn rebuild_cream_api_contents(
+    current_contents: &str,
+    dlc_states: &HashMap<String, (bool, String)>,
+) -> Vec<String> {
     // Keep track of processed DLCs to avoid duplicates
     let mut processed_dlcs = HashSet::new();
 
@@ -184,7 +218,7 @@ pub fn update_dlc_configuration(
             in_dlc_section = false;
 
             // Before leaving the DLC section, add any DLCs that weren't processed yet
-            for (appid, (enabled, name)) in &dlc_states {
+            for (appid, (enabled, name)) in dlc_states {
                 if !processed_dlcs.contains(appid) {
                     if *enabled {
                         new_contents.push(format!("{} = {}", appid, name));
@@ -247,7 +281,7 @@ pub fn update_dlc_configuration(
 
     // If we never left the DLC section, make sure we add any unprocessed DLCs
     if in_dlc_section {
-        for (appid, (enabled, name)) in &dlc_states {
+        for (appid, (enabled, name)) in dlc_states {
             if !processed_dlcs.contains(appid) {
                 if *enabled {
                     new_contents.push(format!("{} = {}", appid, name));
@@ -258,8 +292,113 @@ pub fn update_dlc_configuration(
         }
     }
 
-    // Write the updated file
-    match fs::write(&cream_api_path, new_contents.join("\n")) {
+    new_contents
+}
+
+// A structured preview of what `update_dlc_configuration` would change,
+// so the UI can show a confirmation step before anything is written
+#[derive(Serialize, Debug, Clone)]
+pub struct ConfigDiff {
+    pub newly_enabled: Vec<DlcInfoWithState>,
+    pub newly_disabled: Vec<DlcInfoWithState>,
+    pub added: Vec<DlcInfoWithState>,
+    pub unchanged: Vec<DlcInfoWithState>,
+    pub proposed_contents: String,
+}
+
+// Preview what applying `dlcs` would do to cream_api.ini, without writing
+// anything. Runs the same line-reconstruction as `update_dlc_configuration`
+// and categorizes each DLC against its current on-disk state.
+pub fn preview_dlc_configuration(
+    game_path: &str,
+    dlcs: Vec<DlcInfoWithState>,
+) -> Result<ConfigDiff, String> {
+    info!("Previewing DLC configuration change for {}", game_path);
+
+    let cream_api_path = Path::new(game_path).join("cream_api.ini");
+    if !cream_api_path.exists() {
+        return Err(format!(
+            "cream_api.ini not found at {}",
+            cream_api_path.display()
+        ));
+    }
+
+    let current_contents = fs::read_to_string(&cream_api_path)
+        .map_err(|e| format!("Failed to read cream_api.ini: {}", e))?;
+
+    let existing_dlcs = get_all_dlcs(game_path)?;
+    let existing_by_appid: HashMap<&str, bool> = existing_dlcs
+        .iter()
+        .map(|dlc| (dlc.appid.as_str(), dlc.enabled))
+        .collect();
+
+    let dlc_states: HashMap<String, (bool, String)> = dlcs
+        .iter()
+        .map(|dlc| (dlc.appid.clone(), (dlc.enabled, dlc.name.clone())))
+        .collect();
+
+    let proposed_contents = rebuild_cream_api_contents(&current_contents, &dlc_states).join("\n");
+
+    let mut diff = ConfigDiff {
+        newly_enabled: Vec::new(),
+        newly_disabled: Vec::new(),
+        added: Vec::new(),
+        unchanged: Vec::new(),
+        proposed_contents,
+    };
+
+    for dlc in dlcs {
+        match existing_by_appid.get(dlc.appid.as_str()) {
+            Some(&was_enabled) if was_enabled != dlc.enabled => {
+                if dlc.enabled {
+                    diff.newly_enabled.push(dlc);
+                } else {
+                    diff.newly_disabled.push(dlc);
+                }
+            }
+            Some(_) => diff.unchanged.push(dlc),
+            None => diff.added.push(dlc),
+        }
+    }
+
+    Ok(diff)
+}
+
+// Update the cream_api.ini file with the user's DLC selections
+pub fn update_dlc_configuration(
+    game_path: &str,
+    dlcs: Vec<DlcInfoWithState>,
+) -> Result<(), String> {
+    info!("Updating DLC configuration for {}", game_path);
+
+    let cream_api_path = Path::new(game_path).join("cream_api.ini");
+    if !cream_api_path.exists() {
+        return Err(format!(
+            "cream_api.ini not found at {}",
+            cream_api_path.display()
+        ));
+    }
+
+    // Read the current file contents
+    let current_contents = match fs::read_to_string(&cream_api_path) {
+        Ok(c) => c,
+        Err(e) => return Err(format!("Failed to read cream_api.ini: {}", e)),
+    };
+
+    // Create a mapping of DLC appid to its state for easy lookup
+    let dlc_states: HashMap<String, (bool, String)> = dlcs
+        .iter()
+        .map(|dlc| (dlc.appid.clone(), (dlc.enabled, dlc.name.clone())))
+        .collect();
+
+    let new_contents = rebuild_cream_api_contents(&current_contents, &dlc_states);
+
+    // Write the updated file atomically, backing up the previous contents
+    // first so an interrupted write can't leave a truncated cream_api.ini
+    match crate::installer::atomic_write_with_backup(
+        &cream_api_path,
+        new_contents.join("\n").as_bytes(),
+    ) {
         Ok(_) => {
             info!(
                 "Successfully updated DLC configuration at {}",
@@ -304,6 +443,20 @@ pub async fn install_cream_with_dlcs(
         game.title, game_id
     );
 
+    // Precondition check: log the current install state before touching
+    // anything on disk, so an install triggered mid-repair or mid-update is
+    // traceable rather than inferred after the fact from file diffs
+    match crate::unlockers::get_install_state(&game.path) {
+        Ok(state) => info!(
+            "Current CreamLinux install state for '{}': {:?}",
+            game.title, state
+        ),
+        Err(e) => warn!(
+            "Failed to determine CreamLinux install state for '{}': {}",
+            game.title, e
+        ),
+    }
+
     // Convert DlcInfoWithState to installer::DlcInfo for those that are enabled
     let enabled_dlcs = selected_dlcs
         .iter()
@@ -311,6 +464,7 @@ pub async fn install_cream_with_dlcs(
         .map(|dlc| crate::installer::DlcInfo {
             appid: dlc.appid.clone(),
             name: dlc.name.clone(),
+            owned: dlc.owned,
         })
         .collect::<Vec<_>>();
 
@@ -324,30 +478,66 @@ pub async fn install_cream_with_dlcs(
         .await
         .map_err(|e| format!("Failed to install CreamLinux binaries: {}", e))?;
 
-    // Write cream_api.ini with DLCs
-    let cream_api_path = Path::new(&game_path).join("cream_api.ini");
-    let mut config = String::new();
-
-    config.push_str(&format!("APPID = {}\n[config]\n", game_id));
-    config.push_str("issubscribedapp_on_false_use_real = true\n");
-    config.push_str("[methods]\n");
-    config.push_str("disable_steamapps_issubscribedapp = false\n");
-    config.push_str("[dlc]\n");
-
-    for dlc in &enabled_dlcs {
-        config.push_str(&format!("{} = {}\n", dlc.appid, dlc.name));
-    }
-
-    fs::write(&cream_api_path, config)
+    // Write cream_api.ini with DLCs, sharing the same writer `install_creamlinux`
+    // uses so the two install paths can't drift on file format or atomicity
+    crate::installer::write_cream_api_ini(&game_path, &game_id, &enabled_dlcs, false)
         .map_err(|e| format!("Failed to write cream_api.ini: {}", e))?;
 
     // Update version manifest
     let cached_versions = crate::cache::read_versions()?;
-    crate::cache::update_game_creamlinux_version(&game_path, cached_versions.creamlinux.latest)?;
+    crate::cache::update_game_creamlinux_version(&game_path, cached_versions.creamlinux.active)?;
 
     info!(
         "CreamLinux installation completed successfully for game: {}",
         game.title
     );
     Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rebuild_toggles_existing_dlc_state() {
+        let contents = "[dlc]\n123 = Cool DLC\n# 456 = Other DLC\n";
+        let mut dlc_states = HashMap::new();
+        dlc_states.insert("123".to_string(), (false, "Cool DLC".to_string()));
+        dlc_states.insert("456".to_string(), (true, "Other DLC".to_string()));
+
+        let result = rebuild_cream_api_contents(contents, &dlc_states).join("\n");
+
+        assert!(result.contains("# 123 = Cool DLC"));
+        assert!(result.contains("456 = Other DLC"));
+        assert!(!result.contains("# 456"));
+    }
+
+    #[test]
+    fn test_rebuild_appends_unprocessed_dlcs_before_next_section() {
+        let contents = "[dlc]\n123 = Existing DLC\n[methods]\nfoo = bar\n";
+        let mut dlc_states = HashMap::new();
+        dlc_states.insert("123".to_string(), (true, "Existing DLC".to_string()));
+        dlc_states.insert("999".to_string(), (true, "New DLC".to_string()));
+
+        let lines = rebuild_cream_api_contents(contents, &dlc_states);
+
+        // The new DLC must land inside [dlc], before the next section header
+        let dlc_section_end = lines.iter().position(|l| l == "[methods]").unwrap();
+        assert!(lines[..dlc_section_end].iter().any(|l| l.contains("999")));
+    }
+
+    #[test]
+    fn test_rebuild_appends_unprocessed_dlcs_when_dlc_section_is_last() {
+        // No section follows [dlc], so the "leaving the section" branch never
+        // runs - the trailing unprocessed-DLC pass at the end of the file
+        // is what has to catch this.
+        let contents = "[dlc]\n123 = Existing DLC\n";
+        let mut dlc_states = HashMap::new();
+        dlc_states.insert("123".to_string(), (true, "Existing DLC".to_string()));
+        dlc_states.insert("999".to_string(), (true, "New DLC".to_string()));
+
+        let result = rebuild_cream_api_contents(contents, &dlc_states).join("\n");
+
+        assert!(result.contains("999 = New DLC"));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,88 @@
+// Structured error type for the installer layer.
+//
+// Tauri commands still hand plain `String` errors to the frontend, which
+// loses all context. `InstallerError` (like the cache and unlocker layers'
+// own error types) carries enough structure for the UI to branch on the
+// error kind instead of matching on English text.
+
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum InstallerError {
+    #[error("network request failed: {0}")]
+    SteamApi(#[from] reqwest::Error),
+
+    #[error("rate limited by Steam, please try again shortly")]
+    RateLimited,
+
+    #[error("failed to parse DLC data: {0}")]
+    DlcParse(String),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error("unlocker install failed: {0}")]
+    UnlockerInstall(String),
+
+    #[error("operation cancelled by user")]
+    Cancelled,
+
+    #[error("Proton prefix not found — launch the game at least once first")]
+    PrefixNotExists,
+
+    #[error("expected Steam API DLL(s) not found in the game directory")]
+    ApiDllsMissing,
+
+    #[error("Steamworks API unavailable: {0}")]
+    SteamworksUnavailable(String),
+
+    #[error("{0}")]
+    Other(String),
+}
+
+impl InstallerError {
+    // A stable, machine-readable identifier the frontend can match on
+    pub fn code(&self) -> &'static str {
+        match self {
+            InstallerError::SteamApi(_) => "steam_api",
+            InstallerError::RateLimited => "rate_limited",
+            InstallerError::DlcParse(_) => "dlc_parse",
+            InstallerError::Io(_) => "io",
+            InstallerError::UnlockerInstall(_) => "unlocker_install",
+            InstallerError::Cancelled => "cancelled",
+            InstallerError::PrefixNotExists => "prefix_not_exists",
+            InstallerError::ApiDllsMissing => "api_dlls_missing",
+            InstallerError::SteamworksUnavailable(_) => "steamworks_unavailable",
+            InstallerError::Other(_) => "other",
+        }
+    }
+}
+
+// Tauri commands still return `Result<_, String>`, so callers can propagate
+// an `InstallerError` with `?` without an explicit `.map_err`.
+impl From<InstallerError> for String {
+    fn from(err: InstallerError) -> Self {
+        err.to_string()
+    }
+}
+
+impl From<String> for InstallerError {
+    fn from(s: String) -> Self {
+        InstallerError::Other(s)
+    }
+}
+
+// Serialized as `{ "code": "...", "message": "..." }` for the frontend
+impl Serialize for InstallerError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("InstallerError", 2)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
@@ -1,8 +1,8 @@
 // This module contains helper functions for file operations during installation
 
 use std::fs;
-use std::io;
-use std::path::Path;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 
 // Copy a file with backup
 #[allow(dead_code)]
@@ -18,7 +18,6 @@ pub fn copy_with_backup(src: &Path, dest: &Path) -> io::Result<()> {
 }
 
 // Safely remove a file (doesn't error if it doesn't exist)
-#[allow(dead_code)]
 pub fn safe_remove(path: &Path) -> io::Result<()> {
     if path.exists() {
         fs::remove_file(path)?;
@@ -26,6 +25,63 @@ pub fn safe_remove(path: &Path) -> io::Result<()> {
     Ok(())
 }
 
+// Append a suffix to a path's filename, e.g. `cream_api.ini` -> `cream_api.ini.tmp`
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut file_name = path.as_os_str().to_os_string();
+    file_name.push(suffix);
+    PathBuf::from(file_name)
+}
+
+// Atomically write `contents` to `path`, keeping a `.bak` backup of whatever
+// was there before so a crash mid-write can be recovered from.
+//
+// `contents` is written to a sibling `<path>.tmp` file and fsynced, any
+// existing file at `path` is copied to `<path>.bak`, and only then is the
+// tmp file renamed over `path` - `fs::rename` is atomic within the same
+// filesystem, so a reader never observes a truncated or half-written file.
+// If anything fails after the backup is taken, the backup is restored.
+pub fn atomic_write_with_backup(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let tmp_path = with_suffix(path, ".tmp");
+    let backup_path = with_suffix(path, ".bak");
+
+    {
+        let mut tmp_file = fs::File::create(&tmp_path)?;
+        tmp_file.write_all(contents)?;
+        tmp_file.sync_all()?;
+    }
+
+    if path.exists() {
+        if let Err(e) = fs::copy(path, &backup_path) {
+            safe_remove(&tmp_path)?;
+            return Err(e);
+        }
+    }
+
+    if let Err(e) = fs::rename(&tmp_path, path) {
+        safe_remove(&tmp_path)?;
+        if backup_path.exists() {
+            let _ = fs::copy(&backup_path, path);
+        }
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+// Restore `path` from the `.bak` sibling left by `atomic_write_with_backup`.
+pub fn restore_backup(path: &Path) -> io::Result<()> {
+    let backup_path = with_suffix(path, ".bak");
+    if !backup_path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("no backup found at {}", backup_path.display()),
+        ));
+    }
+
+    fs::copy(&backup_path, path)?;
+    Ok(())
+}
+
 // Make a file executable (Unix only)
 #[cfg(unix)]
 #[allow(dead_code)]
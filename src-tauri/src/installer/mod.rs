@@ -1,21 +1,124 @@
 mod file_ops;
+mod steam_client;
+mod transaction;
+
+pub(crate) use file_ops::{atomic_write_with_backup, restore_backup};
+pub(crate) use transaction::{
+    detect_incomplete_install, uninstall_via_manifest, InstallTransaction,
+};
 
 use crate::cache::{
     remove_creamlinux_version, remove_smokeapi_version,
     update_game_creamlinux_version, update_game_smokeapi_version,
 };
+use crate::error::InstallerError;
 use crate::unlockers::{CreamLinux, SmokeAPI, Unlocker};
 use crate::AppState;
+use futures::stream::{self, StreamExt};
 use log::{error, info, warn};
 use reqwest;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::fs;
 use std::path::Path;
-use std::sync::atomic::Ordering;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 use tauri::Manager;
 use tauri::{AppHandle, Emitter};
+use tokio::sync::Semaphore;
+
+// Default number of DLC detail requests to have in flight at once
+const DEFAULT_DLC_CONCURRENCY: usize = 8;
+
+// Retry a rate-limited DLC request this many times before giving up on it
+const MAX_DLC_RETRIES: u32 = 4;
+
+// How long a halved concurrency limit stays in effect before we try
+// restoring it to the original ceiling
+const DLC_THROTTLE_COOLDOWN_SECS: u64 = 30;
+
+// Shared concurrency limiter for a DLC detail fetch batch: starts at
+// `DEFAULT_DLC_CONCURRENCY` in-flight requests and halves itself (down to a
+// floor of 1) each time Steam answers with 429, restoring the original
+// ceiling once `DLC_THROTTLE_COOLDOWN_SECS` have passed since the 429 that
+// started the cooldown. A single observed 429 thus slows every fetch still
+// waiting for a permit, on top of the per-DLC retry backoff in
+// `fetch_dlc_info`.
+struct AdaptiveLimiter {
+    semaphore: Arc<Semaphore>,
+    current: AtomicUsize,
+    ceiling: usize,
+    cooling_down: AtomicBool,
+}
+
+impl AdaptiveLimiter {
+    fn new(ceiling: usize) -> Arc<Self> {
+        Arc::new(Self {
+            semaphore: Arc::new(Semaphore::new(ceiling)),
+            current: AtomicUsize::new(ceiling),
+            ceiling,
+            cooling_down: AtomicBool::new(false),
+        })
+    }
+
+    // Halve the permit count and schedule a one-shot restore after the
+    // cooldown, unless one is already pending.
+    fn throttle(self: &Arc<Self>) {
+        let current = self.current.load(Ordering::SeqCst);
+        let reduced = (current / 2).max(1);
+        if reduced < current
+            && self
+                .current
+                .compare_exchange(current, reduced, Ordering::SeqCst, Ordering::SeqCst)
+                .is_ok()
+        {
+            // Permits held by in-flight fetches can't be forgotten directly,
+            // so take them back as those fetches finish and drop them.
+            let semaphore = Arc::clone(&self.semaphore);
+            let excess = (current - reduced) as u32;
+            tokio::spawn(async move {
+                if let Ok(permits) = semaphore.acquire_many_owned(excess).await {
+                    permits.forget();
+                }
+            });
+            warn!(
+                "Rate limited by Steam; reducing DLC fetch concurrency from {} to {}",
+                current, reduced
+            );
+        }
+
+        if self
+            .cooling_down
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok()
+        {
+            let this = Arc::clone(self);
+            tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_secs(DLC_THROTTLE_COOLDOWN_SECS)).await;
+                this.restore();
+                this.cooling_down.store(false, Ordering::SeqCst);
+            });
+        }
+    }
+
+    // Bring the permit count back up to the ceiling once the cooldown has elapsed.
+    fn restore(&self) {
+        let current = self.current.load(Ordering::SeqCst);
+        if current < self.ceiling
+            && self
+                .current
+                .compare_exchange(current, self.ceiling, Ordering::SeqCst, Ordering::SeqCst)
+                .is_ok()
+        {
+            self.semaphore.add_permits(self.ceiling - current);
+            info!(
+                "Cooldown elapsed; restoring DLC fetch concurrency to {}",
+                self.ceiling
+            );
+        }
+    }
+}
 
 // Type of installer
 #[derive(Debug, Clone, Copy)]
@@ -29,6 +132,42 @@ pub enum InstallerType {
 pub enum InstallerAction {
     Install,
     Uninstall,
+    Update,
+}
+
+// An installed unlocker version that is older than the cached latest
+#[derive(Serialize, Debug, Clone)]
+pub struct UpdateAvailable {
+    pub from: String,
+    pub to: String,
+}
+
+// Compare the per-game manifest version against the cached latest and report
+// whether an in-place update is available for the given installer.
+pub fn check_update_available(
+    installer_type: InstallerType,
+    game: &Game,
+) -> Result<Option<UpdateAvailable>, InstallerError> {
+    let manifest = crate::cache::read_manifest(&game.path)?;
+    let cached_versions = crate::cache::read_versions()?;
+
+    let outdated = match installer_type {
+        InstallerType::Cream => manifest.is_creamlinux_outdated(&cached_versions.creamlinux.active),
+        InstallerType::Smoke => manifest.is_smokeapi_outdated(&cached_versions.smokeapi.active),
+    };
+
+    let (installed, latest) = match installer_type {
+        InstallerType::Cream => (
+            manifest.creamlinux_version,
+            cached_versions.creamlinux.active,
+        ),
+        InstallerType::Smoke => (manifest.smokeapi_version, cached_versions.smokeapi.active),
+    };
+
+    Ok(match installed {
+        Some(from) if outdated => Some(UpdateAvailable { from, to: latest }),
+        _ => None,
+    })
 }
 
 // DLC Information structure
@@ -36,6 +175,11 @@ pub enum InstallerAction {
 pub struct DlcInfo {
     pub appid: String,
     pub name: String,
+    // Whether the user owns this DLC: as reported by a running Steam client,
+    // or else whether its appmanifest is installed locally. Defaults to false
+    // when deserializing older cache entries that predate this field.
+    #[serde(default)]
+    pub owned: bool,
 }
 
 // Struct to hold installation instructions for the frontend
@@ -59,6 +203,8 @@ pub struct Game {
     pub cream_installed: bool,
     pub smoke_installed: bool,
     pub installing: bool,
+    pub source: crate::searcher::GameSource,
+    pub compat_tool: Option<String>,
 }
 
 // Emit a progress update to the frontend
@@ -88,6 +234,20 @@ pub fn emit_progress(
     }
 }
 
+// Emit a structured, machine-readable error so the frontend can branch on
+// `error.code` instead of matching on the message text.
+pub fn emit_error(app_handle: &AppHandle, title: &str, error: &InstallerError) {
+    let payload = json!({
+        "title": title,
+        "error": error,
+        "complete": true,
+    });
+
+    if let Err(e) = app_handle.emit("installation-error", payload) {
+        warn!("Failed to emit error event: {}", e);
+    }
+}
+
 // Process a single game action (install/uninstall Cream/Smoke)
 pub async fn process_action(
     game_id: String,
@@ -95,7 +255,7 @@ pub async fn process_action(
     action: InstallerAction,
     game: Game,
     app_handle: AppHandle,
-) -> Result<(), String> {
+) -> Result<(), InstallerError> {
     match (installer_type, action) {
         (InstallerType::Cream, InstallerAction::Install) => {
             install_creamlinux(game_id, game, app_handle).await
@@ -109,6 +269,12 @@ pub async fn process_action(
         (InstallerType::Smoke, InstallerAction::Uninstall) => {
             uninstall_smokeapi(game, app_handle).await
         }
+        (InstallerType::Cream, InstallerAction::Update) => {
+            update_creamlinux(game_id, game, app_handle).await
+        }
+        (InstallerType::Smoke, InstallerAction::Update) => {
+            update_smokeapi(game, app_handle).await
+        }
     }
 }
 
@@ -117,9 +283,11 @@ async fn install_creamlinux(
     game_id: String,
     game: Game,
     app_handle: AppHandle,
-) -> Result<(), String> {
+) -> Result<(), InstallerError> {
     if !game.native {
-        return Err("CreamLinux can only be installed on native Linux games".to_string());
+        return Err(InstallerError::Other(
+            "CreamLinux can only be installed on native Linux games".to_string(),
+        ));
     }
 
     info!("Installing CreamLinux for game: {}", game.title);
@@ -136,11 +304,11 @@ async fn install_creamlinux(
     );
 
     // Fetch DLC list
-    let dlcs = match fetch_dlc_details(&game_id).await {
+    let dlcs = match fetch_dlc_details(&game_id, &game.path, false).await {
         Ok(dlcs) => dlcs,
         Err(e) => {
             error!("Failed to fetch DLC details: {}", e);
-            return Err(format!("Failed to fetch DLC details: {}", e));
+            return Err(e);
         }
     };
 
@@ -160,7 +328,9 @@ async fn install_creamlinux(
     // Install CreamLinux binaries from cache
     CreamLinux::install_to_game(&game.path, &game_id)
         .await
-        .map_err(|e| format!("Failed to install CreamLinux: {}", e))?;
+        .map_err(|e| {
+            InstallerError::UnlockerInstall(format!("Failed to install CreamLinux: {}", e))
+        })?;
 
     emit_progress(
         &app_handle,
@@ -173,11 +343,11 @@ async fn install_creamlinux(
     );
 
     // Write cream_api.ini with DLCs
-    write_cream_api_ini(&game.path, &game_id, &dlcs)?;
+    write_cream_api_ini(&game.path, &game_id, &dlcs, true)?;
 
     // Update version manifest
     let cached_versions = crate::cache::read_versions()?;
-    update_game_creamlinux_version(&game.path, cached_versions.creamlinux.latest)?;
+    update_game_creamlinux_version(&game.path, cached_versions.creamlinux.active)?;
 
     // Emit completion with instructions
     let instructions = InstallationInstructions {
@@ -202,9 +372,11 @@ async fn install_creamlinux(
 }
 
 // Uninstall CreamLinux from a game
-async fn uninstall_creamlinux(game: Game, app_handle: AppHandle) -> Result<(), String> {
+async fn uninstall_creamlinux(game: Game, app_handle: AppHandle) -> Result<(), InstallerError> {
     if !game.native {
-        return Err("CreamLinux can only be uninstalled from native Linux games".to_string());
+        return Err(InstallerError::Other(
+            "CreamLinux can only be uninstalled from native Linux games".to_string(),
+        ));
     }
 
     let game_title = game.title.clone();
@@ -222,7 +394,9 @@ async fn uninstall_creamlinux(game: Game, app_handle: AppHandle) -> Result<(), S
 
     CreamLinux::uninstall_from_game(&game.path, &game.id)
         .await
-        .map_err(|e| format!("Failed to uninstall CreamLinux: {}", e))?;
+        .map_err(|e| {
+            InstallerError::UnlockerInstall(format!("Failed to uninstall CreamLinux: {}", e))
+        })?;
 
     // Remove version from manifest
     remove_creamlinux_version(&game.path)?;
@@ -241,12 +415,104 @@ async fn uninstall_creamlinux(game: Game, app_handle: AppHandle) -> Result<(), S
     Ok(())
 }
 
+// Refresh an already-installed CreamLinux to the latest cached version,
+// leaving the user's `cream_api.ini` DLC selections untouched.
+async fn update_creamlinux(
+    game_id: String,
+    game: Game,
+    app_handle: AppHandle,
+) -> Result<(), InstallerError> {
+    if !game.native {
+        return Err(InstallerError::Other(
+            "CreamLinux can only be updated on native Linux games".to_string(),
+        ));
+    }
+
+    let game_title = game.title.clone();
+    info!("Updating CreamLinux for game: {}", game_title);
+
+    emit_progress(
+        &app_handle,
+        &format!("Updating CreamLinux for {}", game_title),
+        "Refreshing CreamLinux files from cache...",
+        50.0,
+        false,
+        false,
+        None,
+    );
+
+    // Only refresh the binaries - cream_api.ini is left alone so the user's
+    // DLC selections survive the update
+    CreamLinux::install_to_game(&game.path, &game_id)
+        .await
+        .map_err(|e| {
+            InstallerError::UnlockerInstall(format!("Failed to update CreamLinux: {}", e))
+        })?;
+
+    let cached_versions = crate::cache::read_versions()?;
+    update_game_creamlinux_version(&game.path, cached_versions.creamlinux.active)?;
+
+    emit_progress(
+        &app_handle,
+        &format!("Update Completed: {}", game_title),
+        "CreamLinux has been updated successfully!",
+        100.0,
+        true,
+        false,
+        None,
+    );
+
+    info!("CreamLinux update completed for: {}", game_title);
+    Ok(())
+}
+
+// Check that a Proton prefix and the expected Steam API DLL(s) exist before
+// touching the game directory, so a bad install attempt fails fast with a
+// clear reason instead of silently patching nothing.
+fn preflight_smokeapi(game: &Game) -> Result<(), InstallerError> {
+    let game_path = Path::new(&game.path);
+
+    // Steam lays Proton prefixes out at <library>/steamapps/compatdata/<appid>/pfx
+    let steamapps_dir = game_path
+        .parent() // .../steamapps/common
+        .and_then(Path::parent); // .../steamapps
+
+    let prefix_exists = steamapps_dir
+        .map(|steamapps| steamapps.join("compatdata").join(&game.id).join("pfx"))
+        .map(|pfx| pfx.exists())
+        .unwrap_or(false);
+
+    if !prefix_exists {
+        warn!(
+            "No Proton prefix found for {} ({}), game has likely never been launched",
+            game.title, game.id
+        );
+        return Err(InstallerError::PrefixNotExists);
+    }
+
+    for api_file in &game.api_files {
+        if !game_path.join(api_file).exists() {
+            warn!(
+                "Expected Steam API DLL missing for {}: {}",
+                game.title, api_file
+            );
+            return Err(InstallerError::ApiDllsMissing);
+        }
+    }
+
+    Ok(())
+}
+
 // Install SmokeAPI to a game
-async fn install_smokeapi(game: Game, app_handle: AppHandle) -> Result<(), String> {
+async fn install_smokeapi(game: Game, app_handle: AppHandle) -> Result<(), InstallerError> {
     if game.native {
-        return Err("SmokeAPI can only be installed on Proton/Windows games".to_string());
+        return Err(InstallerError::Other(
+            "SmokeAPI can only be installed on Proton/Windows games".to_string(),
+        ));
     }
 
+    preflight_smokeapi(&game)?;
+
     info!("Installing SmokeAPI for game: {}", game.title);
     let game_title = game.title.clone();
 
@@ -266,11 +532,13 @@ async fn install_smokeapi(game: Game, app_handle: AppHandle) -> Result<(), Strin
     // Install SmokeAPI from cache
     SmokeAPI::install_to_game(&game.path, &api_files_str)
         .await
-        .map_err(|e| format!("Failed to install SmokeAPI: {}", e))?;
+        .map_err(|e| {
+            InstallerError::UnlockerInstall(format!("Failed to install SmokeAPI: {}", e))
+        })?;
 
     // Update version manifest
     let cached_versions = crate::cache::read_versions()?;
-    update_game_smokeapi_version(&game.path, cached_versions.smokeapi.latest)?;
+    update_game_smokeapi_version(&game.path, cached_versions.smokeapi.active)?;
 
     emit_progress(
         &app_handle,
@@ -287,9 +555,11 @@ async fn install_smokeapi(game: Game, app_handle: AppHandle) -> Result<(), Strin
 }
 
 // Uninstall SmokeAPI from a game
-async fn uninstall_smokeapi(game: Game, app_handle: AppHandle) -> Result<(), String> {
+async fn uninstall_smokeapi(game: Game, app_handle: AppHandle) -> Result<(), InstallerError> {
     if game.native {
-        return Err("SmokeAPI can only be uninstalled from Proton/Windows games".to_string());
+        return Err(InstallerError::Other(
+            "SmokeAPI can only be uninstalled from Proton/Windows games".to_string(),
+        ));
     }
 
     let game_title = game.title.clone();
@@ -310,7 +580,9 @@ async fn uninstall_smokeapi(game: Game, app_handle: AppHandle) -> Result<(), Str
 
     SmokeAPI::uninstall_from_game(&game.path, &api_files_str)
         .await
-        .map_err(|e| format!("Failed to uninstall SmokeAPI: {}", e))?;
+        .map_err(|e| {
+            InstallerError::UnlockerInstall(format!("Failed to uninstall SmokeAPI: {}", e))
+        })?;
 
     // Remove version from manifest
     remove_smokeapi_version(&game.path)?;
@@ -329,9 +601,59 @@ async fn uninstall_smokeapi(game: Game, app_handle: AppHandle) -> Result<(), Str
     Ok(())
 }
 
-// Fetch DLC details from Steam API (simple version without progress)
-pub async fn fetch_dlc_details(app_id: &str) -> Result<Vec<DlcInfo>, String> {
-    let client = reqwest::Client::new();
+// Refresh an already-installed SmokeAPI to the latest cached version
+async fn update_smokeapi(game: Game, app_handle: AppHandle) -> Result<(), InstallerError> {
+    if game.native {
+        return Err(InstallerError::Other(
+            "SmokeAPI can only be updated on Proton/Windows games".to_string(),
+        ));
+    }
+
+    preflight_smokeapi(&game)?;
+
+    let game_title = game.title.clone();
+    info!("Updating SmokeAPI for game: {}", game_title);
+
+    emit_progress(
+        &app_handle,
+        &format!("Updating SmokeAPI for {}", game_title),
+        "Refreshing SmokeAPI files from cache...",
+        50.0,
+        false,
+        false,
+        None,
+    );
+
+    let api_files_str = game.api_files.join(",");
+
+    SmokeAPI::install_to_game(&game.path, &api_files_str)
+        .await
+        .map_err(|e| {
+            InstallerError::UnlockerInstall(format!("Failed to update SmokeAPI: {}", e))
+        })?;
+
+    let cached_versions = crate::cache::read_versions()?;
+    update_game_smokeapi_version(&game.path, cached_versions.smokeapi.active)?;
+
+    emit_progress(
+        &app_handle,
+        &format!("Update Completed: {}", game_title),
+        "SmokeAPI has been updated successfully!",
+        100.0,
+        true,
+        false,
+        None,
+    );
+
+    info!("SmokeAPI update completed for: {}", game_title);
+    Ok(())
+}
+
+// Fetch the list of DLC app IDs for a game from the Steam store API
+async fn fetch_dlc_ids(
+    client: &reqwest::Client,
+    app_id: &str,
+) -> Result<Vec<String>, InstallerError> {
     let base_url = format!(
         "https://store.steampowered.com/api/appdetails?appids={}",
         app_id
@@ -341,98 +663,221 @@ pub async fn fetch_dlc_details(app_id: &str) -> Result<Vec<DlcInfo>, String> {
         .get(&base_url)
         .timeout(Duration::from_secs(10))
         .send()
-        .await
-        .map_err(|e| format!("Failed to fetch game details: {}", e))?;
+        .await?;
+
+    if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return Err(InstallerError::RateLimited);
+    }
 
     if !response.status().is_success() {
-        return Err(format!(
+        return Err(InstallerError::DlcParse(format!(
             "Failed to fetch game details: HTTP {}",
             response.status()
-        ));
+        )));
     }
 
-    let data: serde_json::Value = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse response: {}", e))?;
+    let data: serde_json::Value = response.json().await?;
+
+    // Many appids have no public store entry at all, in which case Steam
+    // answers with `"success": false` and no `data` block - that's not an
+    // error, just a game with no resolvable DLC list
+    let app_entry = data.get(app_id);
+    let success = app_entry
+        .and_then(|app| app.get("success"))
+        .and_then(|s| s.as_bool())
+        .unwrap_or(false);
+
+    if !success {
+        warn!(
+            "Steam appdetails reported no data for app {}, assuming no DLC",
+            app_id
+        );
+        return Ok(Vec::new());
+    }
 
-    let dlc_ids = match data
-        .get(app_id)
+    let dlc_ids = app_entry
         .and_then(|app| app.get("data"))
         .and_then(|data| data.get("dlc"))
-    {
-        Some(dlc_array) => match dlc_array.as_array() {
-            Some(array) => array
+        .and_then(|dlc_array| dlc_array.as_array())
+        .map(|array| {
+            array
                 .iter()
                 .filter_map(|id| id.as_u64().map(|n| n.to_string()))
-                .collect::<Vec<String>>(),
-            _ => Vec::new(),
-        },
-        _ => Vec::new(),
-    };
+                .collect::<Vec<String>>()
+        })
+        .unwrap_or_default();
 
-    info!("Found {} DLCs for game ID {}", dlc_ids.len(), app_id);
-
-    let mut dlc_details = Vec::new();
+    Ok(dlc_ids)
+}
 
-    for dlc_id in dlc_ids {
-        let dlc_url = format!(
-            "https://store.steampowered.com/api/appdetails?appids={}",
-            dlc_id
-        );
+// Fetch a single DLC's details, retrying with exponential backoff if Steam rate-limits us.
+// Returns None (rather than failing the whole batch) if the DLC couldn't be resolved.
+async fn fetch_dlc_info(
+    client: &reqwest::Client,
+    dlc_id: &str,
+    limiter: &Arc<AdaptiveLimiter>,
+) -> Option<DlcInfo> {
+    let dlc_url = format!(
+        "https://store.steampowered.com/api/appdetails?appids={}",
+        dlc_id
+    );
 
-        // Add a small delay to avoid rate limiting
-        tokio::time::sleep(Duration::from_millis(300)).await;
+    let mut backoff = Duration::from_secs(1);
 
-        let dlc_response = client
+    for attempt in 0..=MAX_DLC_RETRIES {
+        let response = match client
             .get(&dlc_url)
             .timeout(Duration::from_secs(10))
             .send()
             .await
-            .map_err(|e| format!("Failed to fetch DLC details: {}", e))?;
-
-        if dlc_response.status().is_success() {
-            let dlc_data: serde_json::Value = dlc_response
-                .json()
-                .await
-                .map_err(|e| format!("Failed to parse DLC response: {}", e))?;
-
-            let dlc_name = match dlc_data
-                .get(&dlc_id)
-                .and_then(|app| app.get("data"))
-                .and_then(|data| data.get("name"))
-            {
-                Some(name) => match name.as_str() {
-                    Some(s) => s.to_string(),
-                    _ => "Unknown DLC".to_string(),
-                },
-                _ => "Unknown DLC".to_string(),
-            };
-
-            info!("Found DLC: {} ({})", dlc_name, dlc_id);
-            dlc_details.push(DlcInfo {
-                appid: dlc_id,
-                name: dlc_name,
-            });
-        } else if dlc_response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
-            // If rate limited, wait longer
-            error!("Rate limited by Steam API, waiting 10 seconds");
-            tokio::time::sleep(Duration::from_secs(10)).await;
+        {
+            Ok(r) => r,
+            Err(e) => {
+                error!("Failed to fetch DLC {}: {}", dlc_id, e);
+                return None;
+            }
+        };
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            limiter.throttle();
+
+            if attempt == MAX_DLC_RETRIES {
+                error!(
+                    "Giving up on DLC {} after {} retries (still rate limited)",
+                    dlc_id, attempt
+                );
+                return None;
+            }
+
+            let jitter = Duration::from_millis(rand::random::<u64>() % 250);
+            warn!(
+                "Rate limited fetching DLC {}, retrying in {:?}",
+                dlc_id, backoff
+            );
+            tokio::time::sleep(backoff + jitter).await;
+            backoff = (backoff * 2).min(Duration::from_secs(8));
+            continue;
         }
+
+        if !response.status().is_success() {
+            warn!("Failed to fetch DLC {}: HTTP {}", dlc_id, response.status());
+            return None;
+        }
+
+        let dlc_data: serde_json::Value = match response.json().await {
+            Ok(d) => d,
+            Err(e) => {
+                error!("Failed to parse DLC {} response: {}", dlc_id, e);
+                return None;
+            }
+        };
+
+        let app_entry = dlc_data.get(dlc_id);
+        let success = app_entry
+            .and_then(|app| app.get("success"))
+            .and_then(|s| s.as_bool())
+            .unwrap_or(false);
+
+        if !success {
+            warn!(
+                "No public store entry for DLC {}, skipping it",
+                dlc_id
+            );
+            return None;
+        }
+
+        let dlc_name = app_entry
+            .and_then(|app| app.get("data"))
+            .and_then(|data| data.get("name"))
+            .and_then(|name| name.as_str())
+            .unwrap_or("Unknown DLC")
+            .to_string();
+
+        info!("Found DLC: {} ({})", dlc_name, dlc_id);
+        return Some(DlcInfo {
+            appid: dlc_id.to_string(),
+            name: dlc_name,
+            owned: false,
+        });
     }
 
+    None
+}
+
+// Mark each DLC as owned or not, using a running Steam client when one is
+// available and otherwise whether its appmanifest is installed locally in
+// the base game's library
+fn classify_dlc_ownership(game_path: &str, dlcs: &mut [DlcInfo]) {
+    let path = Path::new(game_path);
+    for dlc in dlcs.iter_mut() {
+        // Prefer ground-truth ownership from a running Steam client; fall
+        // back to the appmanifest scan when Steamworks isn't available
+        // (no client running, or it failed to initialize).
+        dlc.owned = steam_client::is_dlc_owned(&dlc.appid)
+            .unwrap_or_else(|| crate::searcher::is_app_installed(path, &dlc.appid));
+    }
+}
+
+// Fetch DLC details from Steam API (simple version without progress)
+//
+// Returns the on-disk cached list when it is fresh, unless `force_refresh` is set.
+pub async fn fetch_dlc_details(
+    app_id: &str,
+    game_path: &str,
+    force_refresh: bool,
+) -> Result<Vec<DlcInfo>, InstallerError> {
+    if !force_refresh {
+        if let Some(mut cached) =
+            crate::cache::load_cached_dlcs(app_id, crate::cache::DEFAULT_DLC_CACHE_TTL_SECS)
+        {
+            classify_dlc_ownership(game_path, &mut cached);
+            return Ok(cached);
+        }
+    }
+
+    let client = reqwest::Client::new();
+    let dlc_ids = fetch_dlc_ids(&client, app_id).await?;
+
+    info!("Found {} DLCs for game ID {}", dlc_ids.len(), app_id);
+
+    let limiter = AdaptiveLimiter::new(DEFAULT_DLC_CONCURRENCY);
+    let mut dlc_details: Vec<DlcInfo> = stream::iter(dlc_ids)
+        .map(|dlc_id| {
+            let client = client.clone();
+            let limiter = Arc::clone(&limiter);
+            async move {
+                let _permit = limiter.semaphore.acquire().await.ok()?;
+                fetch_dlc_info(&client, &dlc_id, &limiter).await
+            }
+        })
+        .buffer_unordered(DEFAULT_DLC_CONCURRENCY)
+        .filter_map(|result| async move { result })
+        .collect()
+        .await;
+
     info!(
         "Successfully retrieved details for {} DLCs",
         dlc_details.len()
     );
+
+    if let Err(e) = crate::cache::cache_dlcs(app_id, &dlc_details) {
+        warn!("Failed to persist DLC cache for {}: {}", app_id, e);
+    }
+
+    classify_dlc_ownership(game_path, &mut dlc_details);
+
     Ok(dlc_details)
 }
 
 // Fetch DLC details from Steam API with progress updates
+//
+// Returns the on-disk cached list when it is fresh, unless `force_refresh`
+// is set, matching the short-circuit `fetch_dlc_details` takes.
 pub async fn fetch_dlc_details_with_progress(
     app_id: &str,
     app_handle: &tauri::AppHandle,
-) -> Result<Vec<DlcInfo>, String> {
+    force_refresh: bool,
+) -> Result<Vec<DlcInfo>, InstallerError> {
     info!(
         "Starting DLC details fetch with progress for game ID: {}",
         app_id
@@ -442,156 +887,124 @@ pub async fn fetch_dlc_details_with_progress(
     let state = app_handle.state::<AppState>();
     let should_cancel = state.fetch_cancellation.clone();
 
+    // Needed to classify ownership of each DLC against the local Steam library
+    let game_path = {
+        let games = state.games.lock();
+        games.get(app_id).map(|g| g.path.clone())
+    };
+
+    if !force_refresh {
+        if let Some(mut cached) =
+            crate::cache::load_cached_dlcs(app_id, crate::cache::DEFAULT_DLC_CACHE_TTL_SECS)
+        {
+            if let Some(game_path) = &game_path {
+                classify_dlc_ownership(game_path, &mut cached);
+            }
+            emit_dlc_progress(
+                app_handle,
+                &format!("Completed! Found {} DLCs (cached)", cached.len()),
+                100,
+                None,
+            );
+            return Ok(cached);
+        }
+    }
+
     let client = reqwest::Client::new();
-    let base_url = format!(
-        "https://store.steampowered.com/api/appdetails?appids={}",
-        app_id
-    );
 
     // Emit initial progress
     emit_dlc_progress(app_handle, "Looking up game details...", 5, None);
     info!("Emitted initial DLC progress: 5%");
 
-    let response = client
-        .get(&base_url)
-        .timeout(Duration::from_secs(10))
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch game details: {}", e))?;
-
-    if !response.status().is_success() {
-        let error_msg = format!("Failed to fetch game details: HTTP {}", response.status());
-        error!("{}", error_msg);
-        return Err(error_msg);
-    }
-
-    let data: serde_json::Value = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse response: {}", e))?;
-
-    let dlc_ids = match data
-        .get(app_id)
-        .and_then(|app| app.get("data"))
-        .and_then(|data| data.get("dlc"))
-    {
-        Some(dlc_array) => match dlc_array.as_array() {
-            Some(array) => array
-                .iter()
-                .filter_map(|id| id.as_u64().map(|n| n.to_string()))
-                .collect::<Vec<String>>(),
-            _ => Vec::new(),
-        },
-        _ => Vec::new(),
-    };
+    let dlc_ids = fetch_dlc_ids(&client, app_id).await?;
+    let total_dlcs = dlc_ids.len();
 
-    info!("Found {} DLCs for game ID {}", dlc_ids.len(), app_id);
+    info!("Found {} DLCs for game ID {}", total_dlcs, app_id);
     emit_dlc_progress(
         app_handle,
-        &format!("Found {} DLCs. Fetching details...", dlc_ids.len()),
+        &format!("Found {} DLCs. Fetching details...", total_dlcs),
         10,
         None,
     );
-    info!("Emitted DLC progress: 10%, found {} DLCs", dlc_ids.len());
+    info!("Emitted DLC progress: 10%, found {} DLCs", total_dlcs);
+
+    if total_dlcs == 0 {
+        emit_dlc_progress(app_handle, "Completed! Found 0 DLCs", 100, None);
+        return Ok(Vec::new());
+    }
+
+    let completed = AtomicUsize::new(0);
+    let limiter = AdaptiveLimiter::new(DEFAULT_DLC_CONCURRENCY);
+
+    let mut dlc_stream = stream::iter(dlc_ids)
+        .map(|dlc_id| {
+            let client = client.clone();
+            let limiter = Arc::clone(&limiter);
+            async move {
+                let _permit = limiter.semaphore.acquire().await.ok();
+                fetch_dlc_info(&client, &dlc_id, &limiter).await
+            }
+        })
+        .buffer_unordered(DEFAULT_DLC_CONCURRENCY);
 
     let mut dlc_details = Vec::new();
-    let total_dlcs = dlc_ids.len();
 
-    for (index, dlc_id) in dlc_ids.iter().enumerate() {
-        // Check if cancellation was requested
+    // Drive the stream manually (instead of collecting) so we can check for
+    // cancellation and report progress as each result arrives, out of order.
+    while let Some(result) = dlc_stream.next().await {
         if should_cancel.load(Ordering::SeqCst) {
             info!("DLC fetch cancelled for game {}", app_id);
-            return Err("Operation cancelled by user".to_string());
+            return Err(InstallerError::Cancelled);
         }
 
-        let progress_percent = 10.0 + (index as f32 / total_dlcs as f32) * 90.0;
+        let done = completed.fetch_add(1, Ordering::SeqCst) + 1;
+        let progress_percent = 10.0 + (done as f32 / total_dlcs as f32) * 90.0;
         let progress_rounded = progress_percent as u32;
-        let remaining_dlcs = total_dlcs - index;
-
-        // Estimate time remaining (rough calculation - 300ms per DLC)
-        let est_time_left = if remaining_dlcs > 0 {
-            let seconds = (remaining_dlcs as f32 * 0.3).ceil() as u32;
-            if seconds < 60 {
-                format!("~{} seconds", seconds)
-            } else {
-                format!("~{} minute(s)", (seconds as f32 / 60.0).ceil() as u32)
-            }
+        let remaining = total_dlcs.saturating_sub(done);
+
+        let est_time_left = if remaining > 0 {
+            format!("~{} DLC(s) remaining", remaining)
         } else {
             "almost done".to_string()
         };
 
-        info!(
-            "Processing DLC {}/{} - Progress: {}%",
-            index + 1,
-            total_dlcs,
-            progress_rounded
-        );
-        emit_dlc_progress(
-            app_handle,
-            &format!("Processing DLC {}/{}", index + 1, total_dlcs),
-            progress_rounded,
-            Some(&est_time_left),
-        );
-
-        let dlc_url = format!(
-            "https://store.steampowered.com/api/appdetails?appids={}",
-            dlc_id
-        );
-
-        // Add a small delay to avoid rate limiting
-        tokio::time::sleep(Duration::from_millis(300)).await;
+        match result {
+            Some(mut dlc_info) => {
+                if let Some(game_path) = &game_path {
+                    dlc_info.owned = steam_client::is_dlc_owned(&dlc_info.appid).unwrap_or_else(|| {
+                        crate::searcher::is_app_installed(Path::new(game_path), &dlc_info.appid)
+                    });
+                }
 
-        let dlc_response = client
-            .get(&dlc_url)
-            .timeout(Duration::from_secs(10))
-            .send()
-            .await
-            .map_err(|e| format!("Failed to fetch DLC details: {}", e))?;
-
-        if dlc_response.status().is_success() {
-            let dlc_data: serde_json::Value = dlc_response
-                .json()
-                .await
-                .map_err(|e| format!("Failed to parse DLC response: {}", e))?;
-
-            let dlc_name = match dlc_data
-                .get(&dlc_id)
-                .and_then(|app| app.get("data"))
-                .and_then(|data| data.get("name"))
-            {
-                Some(name) => match name.as_str() {
-                    Some(s) => s.to_string(),
-                    _ => "Unknown DLC".to_string(),
-                },
-                _ => "Unknown DLC".to_string(),
-            };
-
-            info!("Found DLC: {} ({})", dlc_name, dlc_id);
-            let dlc_info = DlcInfo {
-                appid: dlc_id.clone(),
-                name: dlc_name,
-            };
-
-            // Emit each DLC as we find it
-            if let Ok(json) = serde_json::to_string(&dlc_info) {
-                if let Err(e) = app_handle.emit("dlc-found", json) {
-                    warn!("Failed to emit dlc-found event: {}", e);
-                } else {
-                    info!("Emitted dlc-found event for DLC: {}", dlc_id);
+                info!(
+                    "Processed DLC {}/{}: {} ({})",
+                    done, total_dlcs, dlc_info.name, dlc_info.appid
+                );
+                emit_dlc_progress(
+                    app_handle,
+                    &format!("Processed DLC {}/{}", done, total_dlcs),
+                    progress_rounded,
+                    Some(&est_time_left),
+                );
+
+                // Emit each DLC as we find it
+                if let Ok(json) = serde_json::to_string(&dlc_info) {
+                    if let Err(e) = app_handle.emit("dlc-found", json) {
+                        warn!("Failed to emit dlc-found event: {}", e);
+                    }
                 }
-            }
 
-            dlc_details.push(dlc_info);
-        } else if dlc_response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
-            // If rate limited, wait longer
-            error!("Rate limited by Steam API, waiting 10 seconds");
-            emit_dlc_progress(
-                app_handle,
-                "Rate limited by Steam. Waiting...",
-                progress_rounded,
-                None,
-            );
-            tokio::time::sleep(Duration::from_secs(10)).await;
+                dlc_details.push(dlc_info);
+            }
+            None => {
+                warn!("Skipped a DLC ({}/{}) after fetch failure", done, total_dlcs);
+                emit_dlc_progress(
+                    app_handle,
+                    &format!("Processing DLC {}/{}", done, total_dlcs),
+                    progress_rounded,
+                    Some(&est_time_left),
+                );
+            }
         }
     }
 
@@ -608,6 +1021,10 @@ pub async fn fetch_dlc_details_with_progress(
     );
     info!("Emitted final DLC progress: 100%");
 
+    if let Err(e) = crate::cache::cache_dlcs(app_id, &dlc_details) {
+        warn!("Failed to persist DLC cache for {}: {}", app_id, e);
+    }
+
     Ok(dlc_details)
 }
 
@@ -632,8 +1049,15 @@ fn emit_dlc_progress(
     }
 }
 
-// Write cream_api.ini configuration file
-fn write_cream_api_ini(game_path: &str, app_id: &str, dlcs: &[DlcInfo]) -> Result<(), String> {
+// Write cream_api.ini for a game's DLC list. When `skip_owned` is set, DLCs
+// already owned locally are left out entirely - unlocking something the user
+// already owns is just noise and makes troubleshooting harder.
+pub(crate) fn write_cream_api_ini(
+    game_path: &str,
+    app_id: &str,
+    dlcs: &[DlcInfo],
+    skip_owned: bool,
+) -> Result<(), InstallerError> {
     let cream_api_path = Path::new(game_path).join("cream_api.ini");
     let mut config = String::new();
 
@@ -644,11 +1068,13 @@ fn write_cream_api_ini(game_path: &str, app_id: &str, dlcs: &[DlcInfo]) -> Resul
     config.push_str("[dlc]\n");
 
     for dlc in dlcs {
+        if skip_owned && dlc.owned {
+            continue;
+        }
         config.push_str(&format!("{} = {}\n", dlc.appid, dlc.name));
     }
 
-    fs::write(&cream_api_path, config)
-        .map_err(|e| format!("Failed to write cream_api.ini: {}", e))?;
+    atomic_write_with_backup(&cream_api_path, config.as_bytes())?;
 
     info!("Wrote cream_api.ini to {}", cream_api_path.display());
     Ok(())
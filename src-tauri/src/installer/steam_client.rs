@@ -0,0 +1,72 @@
+// Thin wrapper around the `steamworks` crate, used to ask a running Steam
+// client for ground-truth ownership/install data instead of relying solely
+// on locally-scanned appmanifests or the web appdetails endpoint. Both of
+// those stay as the fallback - the Steamworks API only exists while Steam
+// itself is running, so callers must be able to gracefully fall back to
+// their existing check on `InstallerError::SteamworksUnavailable`.
+
+use crate::error::InstallerError;
+use log::{info, warn};
+use parking_lot::Mutex;
+use std::sync::OnceLock;
+
+pub(crate) struct SteamworksClient {
+    client: steamworks::Client,
+}
+
+impl SteamworksClient {
+    fn try_init() -> Result<Self, InstallerError> {
+        let (client, _single) = steamworks::Client::init().map_err(|e| {
+            InstallerError::SteamworksUnavailable(format!(
+                "Steam client not running or API init failed: {}",
+                e
+            ))
+        })?;
+
+        Ok(Self { client })
+    }
+
+    pub(crate) fn is_app_installed(&self, app_id: u32) -> bool {
+        self.client.apps().is_app_installed(steamworks::AppId(app_id))
+    }
+
+    pub(crate) fn is_dlc_installed(&self, app_id: u32) -> bool {
+        self.client.apps().is_dlc_installed(steamworks::AppId(app_id))
+    }
+}
+
+// Lazily initialized, process-wide client. Initialization is attempted once
+// per run - if Steam isn't running the first time we check, we don't keep
+// retrying on every DLC lookup.
+static STEAMWORKS_CLIENT: OnceLock<Mutex<Option<SteamworksClient>>> = OnceLock::new();
+
+fn client_cell() -> &'static Mutex<Option<SteamworksClient>> {
+    STEAMWORKS_CLIENT.get_or_init(|| {
+        Mutex::new(match SteamworksClient::try_init() {
+            Ok(client) => {
+                info!("Steamworks API initialized");
+                Some(client)
+            }
+            Err(e) => {
+                warn!("Steamworks API unavailable, falling back to local scans: {}", e);
+                None
+            }
+        })
+    })
+}
+
+// True if a running Steam client reports `app_id` as owned/installed.
+// `None` means the Steamworks API wasn't available, so the caller should
+// fall back to its own (appmanifest-based) check.
+pub(crate) fn is_dlc_owned(app_id: &str) -> Option<bool> {
+    let app_id: u32 = app_id.parse().ok()?;
+    let guard = client_cell().lock();
+    guard.as_ref().map(|client| client.is_dlc_installed(app_id))
+}
+
+// True if a running Steam client reports the base game as installed.
+pub(crate) fn is_app_installed(app_id: &str) -> Option<bool> {
+    let app_id: u32 = app_id.parse().ok()?;
+    let guard = client_cell().lock();
+    guard.as_ref().map(|client| client.is_app_installed(app_id))
+}
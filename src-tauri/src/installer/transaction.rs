@@ -0,0 +1,286 @@
+// Tracks every file an install step creates or overwrites so a failed
+// install can be unwound, and a later uninstall knows exactly what to
+// remove/restore instead of guessing from a hardcoded file list that
+// drifts as upstream archives change.
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut file_name = path.as_os_str().to_os_string();
+    file_name.push(suffix);
+    PathBuf::from(file_name)
+}
+
+// One file touched by an install transaction. `backup` is the sibling path
+// holding the pre-existing file's contents, if there was one to clobber -
+// `None` means the transaction created the file from scratch, so undoing it
+// just means deleting it.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct ManifestEntry {
+    path: String,
+    backup: Option<String>,
+    // Name of the cached file this entry was installed from, when that
+    // differs from `path`'s own file name (e.g. a cached `smokeapi64.dll`
+    // landing as `steam_api64.dll`). `None` when source and destination
+    // share a name, as CreamLinux's binaries do.
+    #[serde(default)]
+    installed_as: Option<String>,
+    // SHA-256 of the bytes that were written, so a later run can confirm
+    // what's on disk still matches what this transaction installed. Empty
+    // for manifests written before this field existed.
+    #[serde(default)]
+    sha256: String,
+}
+
+// Which component version an install's files came from, so a manifest can be
+// inspected (or this install detected as outdated) without re-downloading
+// anything.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct InstallSource {
+    pub version: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct InstallManifest {
+    entries: Vec<ManifestEntry>,
+    source: Option<InstallSource>,
+    // `false` until `commit()` runs. The manifest is written to disk after
+    // every file, so a crash mid-install leaves it on disk with this still
+    // `false` - the signal `detect_incomplete_install` looks for.
+    #[serde(default = "default_true")]
+    complete: bool,
+}
+
+// Pre-manifest-metadata installs (and anything deserialized before this
+// field existed) are complete by definition - they only ever got written at
+// the very end, via the old one-shot `commit()`.
+fn default_true() -> bool {
+    true
+}
+
+fn manifest_path(game_path: &str, component: &str) -> PathBuf {
+    Path::new(game_path).join(format!(".{}_install_manifest.json", component))
+}
+
+// Accumulates the files a single install touches, persisting the manifest to
+// disk after every file so a crash partway through leaves a record of what
+// was touched - not just an in-memory list that dies with the process.
+pub struct InstallTransaction {
+    game_path: PathBuf,
+    component: &'static str,
+    entries: Vec<ManifestEntry>,
+    source: Option<InstallSource>,
+}
+
+impl InstallTransaction {
+    pub fn new(game_path: &str, component: &'static str) -> Self {
+        Self {
+            game_path: PathBuf::from(game_path),
+            component,
+            entries: Vec::new(),
+            source: None,
+        }
+    }
+
+    // Record which component version the files being installed came from,
+    // so the manifest is self-describing without needing a second lookup.
+    pub fn with_source(mut self, version: impl Into<String>) -> Self {
+        self.source = Some(InstallSource {
+            version: version.into(),
+        });
+        self
+    }
+
+    // Write `contents` to `dest`, backing up whatever was already there so
+    // it can be restored, record the file so it can be unwound, and persist
+    // the manifest immediately (still marked incomplete) so a crash right
+    // after this write is still recoverable.
+    pub fn write_file(&mut self, dest: &Path, contents: &[u8]) -> io::Result<()> {
+        self.write_file_as(dest, None, contents)
+    }
+
+    // Same as `write_file`, but also records which cached source file this
+    // was installed from, for installs where the destination is renamed
+    // (e.g. `smokeapi64.dll` staged in as `steam_api64.dll`).
+    pub fn write_file_as(
+        &mut self,
+        dest: &Path,
+        installed_as: Option<&str>,
+        contents: &[u8],
+    ) -> io::Result<()> {
+        let backup = if dest.exists() {
+            let backup_path = with_suffix(dest, ".install_bak");
+            fs::copy(dest, &backup_path)?;
+            Some(backup_path)
+        } else {
+            None
+        };
+
+        if let Err(e) = fs::write(dest, contents) {
+            if let Some(backup_path) = &backup {
+                let _ = fs::copy(backup_path, dest);
+                let _ = fs::remove_file(backup_path);
+            }
+            return Err(e);
+        }
+
+        self.entries.push(ManifestEntry {
+            path: dest.to_string_lossy().to_string(),
+            backup: backup.map(|p| p.to_string_lossy().to_string()),
+            installed_as: installed_as.map(str::to_string),
+            sha256: crate::checksum::sha256_hex(contents),
+        });
+
+        if let Err(e) = self.persist(false) {
+            warn!(
+                "Failed to journal {} install manifest after writing {}: {}",
+                self.component,
+                dest.display(),
+                e
+            );
+        }
+
+        Ok(())
+    }
+
+    fn persist(&self, complete: bool) -> Result<(), String> {
+        let manifest = InstallManifest {
+            entries: self.entries.clone(),
+            source: self.source.clone(),
+            complete,
+        };
+
+        let content = serde_json::to_string_pretty(&manifest)
+            .map_err(|e| format!("Failed to serialize install manifest: {}", e))?;
+
+        fs::write(
+            manifest_path(&self.game_path.to_string_lossy(), self.component),
+            content,
+        )
+        .map_err(|e| format!("Failed to write install manifest: {}", e))
+    }
+
+    // Mark the manifest complete now that every file in the install has
+    // succeeded. Call this only once.
+    pub fn commit(self) -> Result<(), String> {
+        self.persist(true)?;
+
+        info!(
+            "Committed {} install manifest for {} ({} files)",
+            self.component,
+            self.game_path.display(),
+            self.entries.len()
+        );
+        Ok(())
+    }
+
+    // Undo every file written so far, restoring backups and deleting
+    // freshly-created files, in reverse order of how they were written, then
+    // delete the manifest itself - otherwise it's left on disk forever
+    // marked incomplete, and `detect_incomplete_install` would keep
+    // reporting a crashed install for a game that rollback fully restored.
+    pub fn rollback(self) {
+        warn!(
+            "Rolling back {} install for {} ({} files)",
+            self.component,
+            self.game_path.display(),
+            self.entries.len()
+        );
+
+        for entry in self.entries.into_iter().rev() {
+            let path = Path::new(&entry.path);
+            match &entry.backup {
+                Some(backup) => {
+                    if let Err(e) = fs::copy(backup, path) {
+                        warn!("Failed to restore {} during rollback: {}", entry.path, e);
+                    } else {
+                        let _ = fs::remove_file(backup);
+                    }
+                }
+                None => {
+                    if let Err(e) = fs::remove_file(path) {
+                        warn!("Failed to remove {} during rollback: {}", entry.path, e);
+                    }
+                }
+            }
+        }
+
+        let path = manifest_path(&self.game_path.to_string_lossy(), self.component);
+        if path.exists() {
+            if let Err(e) = fs::remove_file(&path) {
+                warn!("Failed to delete {} install manifest during rollback: {}", self.component, e);
+            }
+        }
+    }
+}
+
+// The install manifest for `component`/`game_path` is still on disk and
+// never got marked complete, meaning the process was killed or crashed
+// partway through. Lets a caller warn the user (and know what version/backup
+// it was mid-install for) instead of silently treating the game directory as
+// if nothing happened.
+pub struct IncompleteInstall {
+    pub files_touched: usize,
+    pub source: Option<InstallSource>,
+}
+
+pub fn detect_incomplete_install(game_path: &str, component: &str) -> Option<IncompleteInstall> {
+    let content = fs::read_to_string(manifest_path(game_path, component)).ok()?;
+    let manifest: InstallManifest = serde_json::from_str(&content).ok()?;
+
+    if manifest.complete {
+        return None;
+    }
+
+    Some(IncompleteInstall {
+        files_touched: manifest.entries.len(),
+        source: manifest.source,
+    })
+}
+
+// Remove/restore every file recorded in `component`'s install manifest for
+// `game_path`, then delete the manifest itself. Returns `Ok(false)` instead
+// of erroring when no manifest exists, so callers can fall back to whatever
+// static removal list they used before this existed.
+pub fn uninstall_via_manifest(game_path: &str, component: &str) -> Result<bool, String> {
+    let path = manifest_path(game_path, component);
+    if !path.exists() {
+        return Ok(false);
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read install manifest: {}", e))?;
+    let manifest: InstallManifest = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse install manifest: {}", e))?;
+
+    for entry in manifest.entries.into_iter().rev() {
+        let entry_path = Path::new(&entry.path);
+        match &entry.backup {
+            Some(backup) => {
+                let backup_path = Path::new(backup);
+                if backup_path.exists() {
+                    if let Err(e) = fs::copy(backup_path, entry_path) {
+                        warn!("Failed to restore {} from manifest: {}", entry.path, e);
+                    } else {
+                        let _ = fs::remove_file(backup_path);
+                    }
+                }
+            }
+            None => {
+                if entry_path.exists() {
+                    if let Err(e) = fs::remove_file(entry_path) {
+                        warn!("Failed to remove {} from manifest: {}", entry.path, e);
+                    }
+                }
+            }
+        }
+    }
+
+    fs::remove_file(&path).map_err(|e| format!("Failed to delete install manifest: {}", e))?;
+    info!("Uninstalled {} via install manifest for {}", component, game_path);
+    Ok(true)
+}
@@ -4,11 +4,18 @@
 )]
 
 mod cache;
+mod checksum;
+mod components;
+mod config;
 mod dlc_manager;
+mod error;
 mod installer;
+mod profiles;
 mod searcher;
+mod state;
 mod unlockers;
 mod smokeapi_config;
+mod vdf;
 
 use crate::unlockers::{CreamLinux, SmokeAPI, Unlocker};
 use dlc_manager::DlcInfoWithState;
@@ -22,6 +29,7 @@ use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use tauri::State;
 use tauri::{Emitter, Manager};
+use tauri_plugin_deep_link::DeepLinkExt;
 use tauri_plugin_updater::Builder as UpdaterBuilder;
 use tokio::time::Instant;
 
@@ -52,10 +60,18 @@ fn get_all_dlcs_command(game_path: String) -> Result<Vec<DlcInfoWithState>, Stri
     dlc_manager::get_all_dlcs(&game_path)
 }
 
+#[tauri::command]
+fn list_available_compat_tools() -> Vec<String> {
+    let paths = searcher::get_default_steam_paths();
+    let libraries = searcher::find_steam_libraries(&paths);
+    searcher::list_compat_tools(&paths, &libraries)
+}
+
 #[tauri::command]
 async fn scan_steam_games(
     state: State<'_, AppState>,
     app_handle: tauri::AppHandle,
+    force_refresh: Option<bool>,
 ) -> Result<Vec<Game>, String> {
     info!("Starting Steam games scan");
     emit_scan_progress(&app_handle, "Locating Steam libraries...", 10);
@@ -87,7 +103,11 @@ async fn scan_steam_games(
         20,
     );
 
-    let games_info = searcher::find_installed_games(&libraries).await;
+    let mut games_info =
+        searcher::find_installed_games(&libraries, force_refresh.unwrap_or(false)).await;
+
+    emit_scan_progress(&app_handle, "Checking Heroic/Legendary libraries...", 85);
+    games_info.extend(searcher::find_launcher_games().await);
 
     emit_scan_progress(
         &app_handle,
@@ -131,6 +151,8 @@ async fn scan_steam_games(
             cream_installed: game_info.cream_installed,
             smoke_installed: game_info.smoke_installed,
             installing: false,
+            source: game_info.source,
+            compat_tool: game_info.compat_tool,
         };
 
         result.push(game.clone());
@@ -143,6 +165,22 @@ async fn scan_steam_games(
         100,
     );
 
+    // Persist the observed install state so it survives restarts; this is a
+    // best-effort cache, not a source of truth, so a failure here shouldn't
+    // fail the scan itself
+    let scan_results = result.iter().map(|game| {
+        (
+            game.id.as_str(),
+            state::GameInstallState {
+                cream_installed: game.cream_installed,
+                smoke_installed: game.smoke_installed,
+            },
+        )
+    });
+    if let Err(e) = state::save_scan_results(scan_results) {
+        warn!("Failed to persist installed-game registry: {}", e);
+    }
+
     info!("Game scan completed successfully");
     Ok(result)
 }
@@ -160,6 +198,17 @@ fn emit_scan_progress(app_handle: &tauri::AppHandle, message: &str, progress: u3
     }
 }
 
+// Forward a `creamlinux://` deep link (e.g. from Steam or a browser) to the
+// frontend as a `deep-link` event rather than acting on it here — the
+// frontend already owns navigation/routing for the rest of the app.
+fn handle_deep_link(app_handle: &tauri::AppHandle, url: &str) {
+    info!("Received deep link: {}", url);
+
+    if let Err(e) = app_handle.emit("deep-link", url) {
+        warn!("Failed to emit deep-link event: {}", e);
+    }
+}
+
 #[tauri::command]
 fn get_game_info(game_id: String, state: State<AppState>) -> Result<Game, String> {
     let games = state.games.lock();
@@ -169,6 +218,70 @@ fn get_game_info(game_id: String, state: State<AppState>) -> Result<Game, String
         .ok_or_else(|| format!("Game with ID {} not found", game_id))
 }
 
+// Batch-compute each known game's install/update status in one pass, so the
+// frontend can color-code the game list without calling check_unlocker_update
+// per game.
+#[tauri::command]
+fn get_game_states(state: State<AppState>) -> Result<HashMap<String, cache::GameState>, String> {
+    let cached_versions = cache::read_versions()?;
+    let games = state.games.lock();
+    Ok(games
+        .iter()
+        .map(|(id, game)| (id.clone(), cache::compute_game_state(game, &cached_versions)))
+        .collect())
+}
+
+// Report exactly what's wrong (or ready) with a game's CreamLinux install, so
+// the frontend can drive install/repair/update actions deterministically.
+#[tauri::command]
+fn get_cream_install_state(
+    game_id: String,
+    state: State<AppState>,
+) -> Result<unlockers::InstallState, String> {
+    let game_path = {
+        let games = state.games.lock();
+        games
+            .get(&game_id)
+            .map(|g| g.path.clone())
+            .ok_or_else(|| format!("Game with ID {} not found", game_id))?
+    };
+
+    unlockers::get_install_state(&game_path)
+}
+
+#[tauri::command]
+fn check_unlocker_update(
+    game_id: String,
+    installer: String,
+    state: State<AppState>,
+) -> Result<Option<installer::UpdateAvailable>, String> {
+    let game = {
+        let games = state.games.lock();
+        games
+            .get(&game_id)
+            .cloned()
+            .ok_or_else(|| format!("Game with ID {} not found", game_id))?
+    };
+
+    let installer_type = match installer.as_str() {
+        "cream" => InstallerType::Cream,
+        "smoke" => InstallerType::Smoke,
+        _ => return Err(format!("Invalid installer: {}", installer)),
+    };
+
+    installer::check_update_available(installer_type, &game).map_err(|e| e.into())
+}
+
+// List the releases available for a component family ("creamlinux" or
+// "smokeapi") so the frontend can offer a version picker instead of always
+// installing latest.
+#[tauri::command]
+async fn list_component_versions(
+    family: String,
+) -> Result<Vec<components::ComponentVersion>, String> {
+    components::list_component_versions(&family).await
+}
+
 #[tauri::command]
 async fn process_game_action(
     game_action: GameAction,
@@ -186,19 +299,25 @@ async fn process_game_action(
     let (installer_type, action) = match game_action.action.as_str() {
         "install_cream" => (InstallerType::Cream, InstallerAction::Install),
         "uninstall_cream" => (InstallerType::Cream, InstallerAction::Uninstall),
+        "update_cream" => (InstallerType::Cream, InstallerAction::Update),
         "install_smoke" => (InstallerType::Smoke, InstallerAction::Install),
         "uninstall_smoke" => (InstallerType::Smoke, InstallerAction::Uninstall),
+        "update_smoke" => (InstallerType::Smoke, InstallerAction::Update),
         _ => return Err(format!("Invalid action: {}", game_action.action)),
     };
 
-    installer::process_action(
+    if let Err(e) = installer::process_action(
         game_action.game_id.clone(),
         installer_type,
         action,
         game.clone(),
         app_handle.clone(),
     )
-    .await?;
+    .await
+    {
+        installer::emit_error(&app_handle, &format!("Action failed for {}", game.title), &e);
+        return Err(e.into());
+    }
 
     let updated_game = {
         let mut games_map = state.games.lock();
@@ -222,6 +341,12 @@ async fn process_game_action(
             (InstallerType::Smoke, InstallerAction::Uninstall) => {
                 game.smoke_installed = false;
             }
+            (InstallerType::Cream, InstallerAction::Update) => {
+                game.cream_installed = true;
+            }
+            (InstallerType::Smoke, InstallerAction::Update) => {
+                game.smoke_installed = true;
+            }
         }
 
         game.installing = false;
@@ -238,24 +363,22 @@ async fn process_game_action(
 #[tauri::command]
 async fn fetch_game_dlcs(
     game_id: String,
+    force_refresh: Option<bool>,
     state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
 ) -> Result<Vec<DlcInfoWithState>, String> {
     info!("Fetching DLC list for game ID: {}", game_id);
 
-    // Fetch DLC data from API
-    match installer::fetch_dlc_details(&game_id).await {
-        Ok(dlcs) => {
-            info!("Successfully fetched {} DLCs for game {}", dlcs.len(), game_id);
-
-            // Convert to DLCInfoWithState for in-memory caching
-            let dlcs_with_state = dlcs
-                .into_iter()
-                .map(|dlc| DlcInfoWithState {
-                    appid: dlc.appid,
-                    name: dlc.name,
-                    enabled: true,
-                })
-                .collect::<Vec<_>>();
+    // Fetch DLC data from Steam (or the persistent cache, unless force_refresh is set)
+    match dlc_manager::fetch_dlcs_from_steam(&game_id, &app_handle, force_refresh.unwrap_or(false))
+        .await
+    {
+        Ok(dlcs_with_state) => {
+            info!(
+                "Successfully fetched {} DLCs for game {}",
+                dlcs_with_state.len(),
+                game_id
+            );
 
             // Update in-memory cache
             let mut dlc_cache = state.dlc_cache.lock();
@@ -271,7 +394,7 @@ async fn fetch_game_dlcs(
         }
         Err(e) => {
             error!("Failed to fetch DLC details: {}", e);
-            Err(format!("Failed to fetch DLC details: {}", e))
+            Err(e)
         }
     }
 }
@@ -292,11 +415,21 @@ fn abort_dlc_fetch(state: State<'_, AppState>, app_handle: tauri::AppHandle) ->
 }
 
 #[tauri::command]
-async fn stream_game_dlcs(game_id: String, app_handle: tauri::AppHandle) -> Result<(), String> {
+async fn stream_game_dlcs(
+    game_id: String,
+    app_handle: tauri::AppHandle,
+    force_refresh: Option<bool>,
+) -> Result<(), String> {
     info!("Streaming DLCs for game ID: {}", game_id);
 
-    // Fetch DLC data from API
-    match installer::fetch_dlc_details_with_progress(&game_id, &app_handle).await {
+    // Fetch DLC data from API (or the persistent cache, unless force_refresh is set)
+    match installer::fetch_dlc_details_with_progress(
+        &game_id,
+        &app_handle,
+        force_refresh.unwrap_or(false),
+    )
+    .await
+    {
         Ok(dlcs) => {
             info!(
                 "Successfully streamed {} DLCs for game {}",
@@ -311,6 +444,7 @@ async fn stream_game_dlcs(game_id: String, app_handle: tauri::AppHandle) -> Resu
                     appid: dlc.appid,
                     name: dlc.name,
                     enabled: true,
+                    owned: dlc.owned,
                 })
                 .collect::<Vec<_>>();
 
@@ -349,6 +483,55 @@ fn clear_caches() -> Result<(), String> {
     Ok(())
 }
 
+// Snapshot of the running environment, meant to be copy-pasted into a bug
+// report rather than consumed programmatically by the frontend
+#[derive(Serialize, Debug, Clone)]
+struct EnvironmentDiagnostics {
+    app_version: String,
+    os: String,
+    arch: String,
+    steam_library_count: usize,
+    cache_dir: Option<String>,
+    config_dir: Option<String>,
+    data_dir: Option<String>,
+    log_path: Option<String>,
+}
+
+#[tauri::command]
+fn get_environment_diagnostics() -> EnvironmentDiagnostics {
+    info!("Collecting environment diagnostics");
+
+    let paths = searcher::get_default_steam_paths();
+    let steam_library_count = searcher::find_steam_libraries(&paths).len();
+
+    let log_path = xdg::BaseDirectories::with_prefix("creamlinux")
+        .and_then(|dirs| dirs.place_cache_file("creamlinux.log"))
+        .ok()
+        .map(|p| p.display().to_string());
+
+    EnvironmentDiagnostics {
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        steam_library_count,
+        cache_dir: cache::get_cache_dir().ok().map(|p| p.display().to_string()),
+        config_dir: config::get_config_dir().ok().map(|p| p.display().to_string()),
+        data_dir: state::get_data_dir().ok().map(|p| p.display().to_string()),
+        log_path,
+    }
+}
+
+// Batch health check across every installed game, for a "is everything up to
+// date" view instead of checking games one at a time.
+#[tauri::command]
+async fn get_game_diagnostics_report() -> Result<Vec<cache::GameReport>, String> {
+    info!("Generating game diagnostics report");
+
+    let paths = searcher::get_default_steam_paths();
+    let libraries = searcher::find_steam_libraries(&paths);
+    cache::scan_installed(&libraries).await
+}
+
 #[tauri::command]
 fn get_enabled_dlcs_command(game_path: String) -> Result<Vec<String>, String> {
     info!("Getting enabled DLCs for: {}", game_path);
@@ -364,6 +547,43 @@ fn update_dlc_configuration_command(
     dlc_manager::update_dlc_configuration(&game_path, dlcs)
 }
 
+#[tauri::command]
+fn preview_dlc_configuration_command(
+    game_path: String,
+    dlcs: Vec<DlcInfoWithState>,
+) -> Result<dlc_manager::ConfigDiff, String> {
+    info!("Previewing DLC configuration for: {}", game_path);
+    dlc_manager::preview_dlc_configuration(&game_path, dlcs)
+}
+
+#[tauri::command]
+fn restore_cream_api_backup(game_path: String) -> Result<(), String> {
+    info!("Restoring cream_api.ini backup for: {}", game_path);
+    dlc_manager::restore_cream_api_backup(&game_path)
+}
+
+#[tauri::command]
+fn save_dlc_profile(
+    game_id: String,
+    name: String,
+    dlcs: Vec<DlcInfoWithState>,
+) -> Result<i64, String> {
+    info!("Saving DLC profile '{}' for game {}", name, game_id);
+    profiles::save_profile(&game_id, &name, &dlcs)
+}
+
+#[tauri::command]
+fn list_dlc_profiles(game_id: String) -> Result<Vec<profiles::ProfileSummary>, String> {
+    info!("Listing DLC profiles for game {}", game_id);
+    profiles::list_profiles(&game_id)
+}
+
+#[tauri::command]
+fn apply_dlc_profile(profile_id: i64, game_path: String) -> Result<(), String> {
+    info!("Applying DLC profile {} to {}", profile_id, game_path);
+    profiles::apply_profile(profile_id, &game_path)
+}
+
 #[tauri::command]
 async fn install_cream_with_dlcs_command(
     game_id: String,
@@ -439,7 +659,7 @@ async fn install_cream_with_dlcs_command(
 #[tauri::command]
 fn read_smokeapi_config(game_path: String) -> Result<Option<smokeapi_config::SmokeAPIConfig>, String> {
     info!("Reading SmokeAPI config for: {}", game_path);
-    smokeapi_config::read_config(&game_path)
+    smokeapi_config::get(&game_path).map_err(Into::into)
 }
 
 #[tauri::command]
@@ -448,13 +668,13 @@ fn write_smokeapi_config(
     config: smokeapi_config::SmokeAPIConfig,
 ) -> Result<(), String> {
     info!("Writing SmokeAPI config for: {}", game_path);
-    smokeapi_config::write_config(&game_path, &config)
+    smokeapi_config::write_config(&game_path, &config).map_err(Into::into)
 }
 
 #[tauri::command]
 fn delete_smokeapi_config(game_path: String) -> Result<(), String> {
     info!("Deleting SmokeAPI config for: {}", game_path);
-    smokeapi_config::delete_config(&game_path)
+    smokeapi_config::delete_config(&game_path).map_err(Into::into)
 }
 
 #[tauri::command]
@@ -637,19 +857,48 @@ fn main() {
     info!("Initializing CreamLinux application");
 
     tauri::Builder::default()
+        // Must be registered before any other plugin: it's what lets us
+        // intercept a second launch and forward its args here instead of
+        // spawning a second window.
+        .plugin(tauri_plugin_single_instance::init(|app, argv, cwd| {
+            info!(
+                "Blocked a second instance launch, forwarding args: {:?} (cwd: {:?})",
+                argv, cwd
+            );
+
+            if let Some(window) = app.get_webview_window("main") {
+                if let Err(e) = window.unminimize() {
+                    warn!("Failed to unminimize main window: {}", e);
+                }
+                if let Err(e) = window.set_focus() {
+                    warn!("Failed to focus main window: {}", e);
+                }
+            }
+        }))
         .plugin(UpdaterBuilder::new().build())
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
         .invoke_handler(tauri::generate_handler![
             scan_steam_games,
+            list_available_compat_tools,
             get_game_info,
+            get_game_states,
+            get_cream_install_state,
+            check_unlocker_update,
+            list_component_versions,
             process_game_action,
             fetch_game_dlcs,
             stream_game_dlcs,
             get_enabled_dlcs_command,
             update_dlc_configuration_command,
+            preview_dlc_configuration_command,
+            restore_cream_api_backup,
+            save_dlc_profile,
+            list_dlc_profiles,
+            apply_dlc_profile,
             install_cream_with_dlcs_command,
             get_all_dlcs_command,
             clear_caches,
@@ -658,6 +907,8 @@ fn main() {
             write_smokeapi_config,
             delete_smokeapi_config,
             resolve_platform_conflict,
+            get_environment_diagnostics,
+            get_game_diagnostics_report,
         ])
         .setup(|app| {
             info!("Tauri application setup");
@@ -671,6 +922,22 @@ fn main() {
                 }
             }
 
+            // Register the `creamlinux://` URI scheme and forward any
+            // open-url event (app launch or already-running instance) to
+            // the frontend. Registration only applies on Linux/Windows;
+            // macOS picks the scheme up from the bundle's Info.plist.
+            #[cfg(any(target_os = "linux", target_os = "windows"))]
+            if let Err(e) = app.deep_link().register("creamlinux") {
+                warn!("Failed to register creamlinux:// URI scheme: {}", e);
+            }
+
+            let deep_link_handle = app.handle().clone();
+            app.deep_link().on_open_url(move |event| {
+                for url in event.urls() {
+                    handle_deep_link(&deep_link_handle, url.as_str());
+                }
+            });
+
             let app_handle = app.handle().clone();
             let state = AppState {
                 games: Mutex::new(HashMap::new()),
@@ -679,12 +946,33 @@ fn main() {
             };
             app.manage(state);
 
+            // Stream unlocker download progress to the frontend as
+            // `download-progress` events instead of only finding out once
+            // the whole archive has landed
+            let (progress_tx, mut progress_rx) =
+                tokio::sync::mpsc::unbounded_channel::<unlockers::DownloadProgress>();
+            let progress_app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                while let Some(progress) = progress_rx.recv().await {
+                    let payload = serde_json::json!({
+                        "component": progress.component,
+                        "version": progress.version,
+                        "downloaded_bytes": progress.downloaded_bytes,
+                        "total_bytes": progress.total_bytes,
+                        "message": progress.message,
+                    });
+                    if let Err(e) = progress_app_handle.emit("download-progress", payload) {
+                        warn!("Failed to emit download-progress event: {}", e);
+                    }
+                }
+            });
+
             // Initialize cache on startup in a background task
             tauri::async_runtime::spawn(async move {
                 info!("Starting cache initialization...");
 
                 // Step 1: Initialize cache if needed (downloads unlockers)
-                if let Err(e) = cache::initialize_cache().await {
+                if let Err(e) = cache::initialize_cache(Some(progress_tx.clone())).await {
                     error!("Failed to initialize cache: {}", e);
                     return;
                 }
@@ -692,7 +980,7 @@ fn main() {
                 info!("Cache initialized successfully");
 
                 // Step 2: Check for updates
-                match cache::check_and_update_cache().await {
+                match cache::check_and_update_cache(Some(progress_tx)).await {
                     Ok(result) => {
                         if result.any_updated() {
                             info!(
@@ -717,6 +1005,12 @@ fn main() {
                                             "Some game updates failed: SmokeAPI failed: {}, CreamLinux failed: {}",
                                             stats.smokeapi_failed, stats.creamlinux_failed
                                         );
+                                        for failure in &stats.failures {
+                                            warn!(
+                                                "  {} ({}): {}",
+                                                failure.game_title, failure.installer, failure.reason
+                                            );
+                                        }
                                     }
                                 }
                                 Err(e) => {
@@ -0,0 +1,158 @@
+// DLC selection profiles: named, reusable sets of DLC toggles per game,
+// stored in a small local SQLite database so a user's choices survive
+// reinstalls instead of only living in the mutable cream_api.ini.
+
+use crate::dlc_manager::DlcInfoWithState;
+use log::info;
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::path::PathBuf;
+
+// A saved profile's identity, without its DLC rows - used for listing
+#[derive(Serialize, Debug, Clone)]
+pub struct ProfileSummary {
+    pub id: i64,
+    pub game_id: String,
+    pub name: String,
+}
+
+// Get the directory the profiles database lives in (~/.local/share/creamlinux)
+fn get_profiles_dir() -> Result<PathBuf, String> {
+    let xdg_dirs = xdg::BaseDirectories::with_prefix("creamlinux")
+        .map_err(|e| format!("Failed to get XDG directories: {}", e))?;
+
+    let data_dir = xdg_dirs
+        .get_data_home()
+        .parent()
+        .ok_or_else(|| "Failed to get data parent directory".to_string())?
+        .join("creamlinux");
+
+    if !data_dir.exists() {
+        std::fs::create_dir_all(&data_dir)
+            .map_err(|e| format!("Failed to create data directory: {}", e))?;
+        info!("Created data directory: {}", data_dir.display());
+    }
+
+    Ok(data_dir)
+}
+
+// Open the profiles database, creating its schema if this is the first run
+fn open_db() -> Result<Connection, String> {
+    let db_path = get_profiles_dir()?.join("profiles.db");
+
+    let conn = Connection::open(&db_path)
+        .map_err(|e| format!("Failed to open profiles database: {}", e))?;
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS profiles (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            game_id TEXT NOT NULL,
+            name TEXT NOT NULL
+        )",
+        [],
+    )
+    .map_err(|e| format!("Failed to create profiles table: {}", e))?;
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS profile_dlcs (
+            profile_id INTEGER NOT NULL REFERENCES profiles(id),
+            appid TEXT NOT NULL,
+            name TEXT NOT NULL,
+            enabled INTEGER NOT NULL
+        )",
+        [],
+    )
+    .map_err(|e| format!("Failed to create profile_dlcs table: {}", e))?;
+
+    Ok(conn)
+}
+
+// Save the current DLC selection as a named profile for this game
+pub fn save_profile(
+    game_id: &str,
+    name: &str,
+    dlcs: &[DlcInfoWithState],
+) -> Result<i64, String> {
+    info!("Saving DLC profile '{}' for game {}", name, game_id);
+
+    let mut conn = open_db()?;
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    tx.execute(
+        "INSERT INTO profiles (game_id, name) VALUES (?1, ?2)",
+        params![game_id, name],
+    )
+    .map_err(|e| format!("Failed to save profile: {}", e))?;
+
+    let profile_id = tx.last_insert_rowid();
+
+    for dlc in dlcs {
+        tx.execute(
+            "INSERT INTO profile_dlcs (profile_id, appid, name, enabled) VALUES (?1, ?2, ?3, ?4)",
+            params![profile_id, dlc.appid, dlc.name, dlc.enabled],
+        )
+        .map_err(|e| format!("Failed to save profile DLC {}: {}", dlc.appid, e))?;
+    }
+
+    tx.commit()
+        .map_err(|e| format!("Failed to commit profile: {}", e))?;
+
+    info!("Saved profile '{}' ({}) with {} DLCs", name, profile_id, dlcs.len());
+    Ok(profile_id)
+}
+
+// List every profile saved for a game, most recently created first
+pub fn list_profiles(game_id: &str) -> Result<Vec<ProfileSummary>, String> {
+    let conn = open_db()?;
+
+    let mut stmt = conn
+        .prepare("SELECT id, game_id, name FROM profiles WHERE game_id = ?1 ORDER BY id DESC")
+        .map_err(|e| format!("Failed to prepare profile query: {}", e))?;
+
+    let profiles = stmt
+        .query_map(params![game_id], |row| {
+            Ok(ProfileSummary {
+                id: row.get(0)?,
+                game_id: row.get(1)?,
+                name: row.get(2)?,
+            })
+        })
+        .map_err(|e| format!("Failed to query profiles: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read profile rows: {}", e))?;
+
+    Ok(profiles)
+}
+
+// Apply a saved profile to a game, writing its stored DLC selections
+// straight into cream_api.ini
+pub fn apply_profile(profile_id: i64, game_path: &str) -> Result<(), String> {
+    info!("Applying DLC profile {} to {}", profile_id, game_path);
+
+    let conn = open_db()?;
+
+    let mut stmt = conn
+        .prepare("SELECT appid, name, enabled FROM profile_dlcs WHERE profile_id = ?1")
+        .map_err(|e| format!("Failed to prepare profile DLC query: {}", e))?;
+
+    let dlcs = stmt
+        .query_map(params![profile_id], |row| {
+            Ok(DlcInfoWithState {
+                appid: row.get(0)?,
+                name: row.get(1)?,
+                enabled: row.get(2)?,
+                owned: false,
+            })
+        })
+        .map_err(|e| format!("Failed to query profile DLCs: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read profile DLC rows: {}", e))?;
+
+    if dlcs.is_empty() {
+        return Err(format!("Profile {} has no saved DLCs", profile_id));
+    }
+
+    crate::dlc_manager::update_dlc_configuration(game_path, dlcs)
+}
@@ -1,6 +1,7 @@
 use log::{debug, error, info, warn};
 use regex::Regex;
-use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io::Read;
 use std::path::{Path, PathBuf};
@@ -8,8 +9,18 @@ use std::sync::Arc;
 use tokio::sync::mpsc;
 use walkdir::WalkDir;
 
+use crate::vdf;
+
+// Which launcher a game was discovered through
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameSource {
+    Steam,
+    HeroicGog,
+    HeroicEpic,
+}
+
 // Game information structure
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameInfo {
     pub id: String,
     pub title: String,
@@ -18,6 +29,8 @@ pub struct GameInfo {
     pub api_files: Vec<String>,
     pub cream_installed: bool,
     pub smoke_installed: bool,
+    pub source: GameSource,
+    pub compat_tool: Option<String>,
 }
 
 // Find potential Steam installation directories
@@ -91,21 +104,28 @@ fn read_steam_registry() -> Option<Vec<PathBuf>> {
             debug!("Found Steam registry at: {}", path.display());
 
             if let Ok(content) = fs::read_to_string(path) {
+                let root = vdf::parse(&content);
+                // registry.vdf nests everything under "Registry" -> "HKCU" ->
+                // "Software" -> "Valve" -> "Steam"
+                let steam_block = root
+                    .get("Registry")
+                    .and_then(|v| v.get("HKCU"))
+                    .and_then(|v| v.get("Software"))
+                    .and_then(|v| v.get("Valve"))
+                    .and_then(|v| v.get("Steam"));
+
                 let mut paths = Vec::new();
 
-                // Extract Steam installation paths
-                let re_steam_path = Regex::new(r#""SteamPath"\s+"([^"]+)""#).unwrap();
-                if let Some(cap) = re_steam_path.captures(&content) {
-                    let steam_path = PathBuf::from(&cap[1]);
-                    paths.push(steam_path);
-                }
+                if let Some(steam_block) = steam_block {
+                    if let Some(steam_path) = steam_block.get_str("SteamPath") {
+                        paths.push(PathBuf::from(steam_path));
+                    }
 
-                // Look for install path
-                let re_install_path = Regex::new(r#""InstallPath"\s+"([^"]+)""#).unwrap();
-                if let Some(cap) = re_install_path.captures(&content) {
-                    let install_path = PathBuf::from(&cap[1]);
-                    if !paths.contains(&install_path) {
-                        paths.push(install_path);
+                    if let Some(install_path) = steam_block.get_str("InstallPath") {
+                        let install_path = PathBuf::from(install_path);
+                        if !paths.contains(&install_path) {
+                            paths.push(install_path);
+                        }
                     }
                 }
 
@@ -121,7 +141,17 @@ fn read_steam_registry() -> Option<Vec<PathBuf>> {
 
 // Find all Steam library folders from base Steam installation paths
 pub fn find_steam_libraries(base_paths: &[PathBuf]) -> Vec<PathBuf> {
+    find_steam_libraries_and_app_map(base_paths).0
+}
+
+// Same traversal as `find_steam_libraries`, but also returns a map of
+// installed appid -> owning library (steamapps dir), built from each
+// libraryfolders.vdf's `apps` sub-block
+pub fn find_steam_libraries_and_app_map(
+    base_paths: &[PathBuf],
+) -> (Vec<PathBuf>, HashMap<String, PathBuf>) {
     let mut libraries = HashSet::new();
+    let mut app_libraries = HashMap::new();
 
     for base_path in base_paths {
         debug!("Looking for Steam libraries in: {}", base_path.display());
@@ -133,7 +163,7 @@ pub fn find_steam_libraries(base_paths: &[PathBuf]) -> Vec<PathBuf> {
             libraries.insert(steamapps_path.clone());
 
             // Check for additional libraries in libraryfolders.vdf
-            parse_library_folders_vdf(&steamapps_path, &mut libraries);
+            parse_library_folders_vdf(&steamapps_path, &mut libraries, &mut app_libraries);
         }
 
         // Also check for steamapps in common locations relative to this path
@@ -148,7 +178,7 @@ pub fn find_steam_libraries(base_paths: &[PathBuf]) -> Vec<PathBuf> {
                 libraries.insert(path.clone());
 
                 // Check for additional libraries in libraryfolders.vdf
-                parse_library_folders_vdf(path, &mut libraries);
+                parse_library_folders_vdf(path, &mut libraries, &mut app_libraries);
             }
         }
     }
@@ -158,11 +188,18 @@ pub fn find_steam_libraries(base_paths: &[PathBuf]) -> Vec<PathBuf> {
     for (i, lib) in result.iter().enumerate() {
         info!("  Library {}: {}", i + 1, lib.display());
     }
-    result
+    (result, app_libraries)
 }
 
-// Parse libraryfolders.vdf to extract additional library paths
-fn parse_library_folders_vdf(steamapps_path: &Path, libraries: &mut HashSet<PathBuf>) {
+// Parse libraryfolders.vdf to extract additional library paths, and record
+// which library owns each installed appid via that entry's `apps` block
+// (appid -> bytes-on-disk), rather than inferring ownership later from where
+// a stray appmanifest happens to be found
+fn parse_library_folders_vdf(
+    steamapps_path: &Path,
+    libraries: &mut HashSet<PathBuf>,
+    app_libraries: &mut HashMap<String, PathBuf>,
+) {
     // Check both possible locations of the VDF file
     let vdf_paths = [
         steamapps_path.join("libraryfolders.vdf"),
@@ -170,60 +207,216 @@ fn parse_library_folders_vdf(steamapps_path: &Path, libraries: &mut HashSet<Path
     ];
 
     for vdf_path in &vdf_paths {
-        if vdf_path.exists() {
-            debug!("Found library folders VDF: {}", vdf_path.display());
-
-            if let Ok(content) = fs::read_to_string(vdf_path) {
-                // Extract library paths using regex for both new and old format VDFs
-                let re_path = Regex::new(r#""path"\s+"([^"]+)""#).unwrap();
-                for cap in re_path.captures_iter(&content) {
-                    let path_str = &cap[1];
-                    let lib_path = PathBuf::from(path_str).join("steamapps");
-
-                    if lib_path.exists() && lib_path.is_dir() && !libraries.contains(&lib_path) {
-                        debug!("Found library from VDF: {}", lib_path.display());
-                        // Clone lib_path before inserting to avoid ownership issues
-                        let lib_path_clone = lib_path.clone();
-                        libraries.insert(lib_path_clone);
-
-                        // Recursively check this library for more libraries
-                        parse_library_folders_vdf(&lib_path, libraries);
-                    }
+        if !vdf_path.exists() {
+            continue;
+        }
+        debug!("Found library folders VDF: {}", vdf_path.display());
+
+        let content = match fs::read_to_string(vdf_path) {
+            Ok(c) => c,
+            Err(e) => {
+                warn!("Failed to read {}: {}", vdf_path.display(), e);
+                continue;
+            }
+        };
+
+        let root = vdf::parse(&content);
+        // Newer libraryfolders.vdf wraps everything in a top-level
+        // "libraryfolders" block; older ones list numbered entries directly
+        let folders = root.get("libraryfolders").unwrap_or(&root);
+
+        for (key, entry) in folders.entries() {
+            // Only numbered entries ("0", "1", ...) are actual libraries
+            if key.parse::<u32>().is_err() {
+                continue;
+            }
+
+            let path_str = match entry.get_str("path") {
+                Some(p) => p,
+                None => continue,
+            };
+            let lib_path = PathBuf::from(path_str).join("steamapps");
+
+            if let Some(apps) = entry.get("apps") {
+                for (appid, _) in apps.entries() {
+                    app_libraries.insert(appid.clone(), lib_path.clone());
                 }
             }
+
+            if lib_path.exists() && lib_path.is_dir() && !libraries.contains(&lib_path) {
+                debug!("Found library from VDF: {}", lib_path.display());
+                libraries.insert(lib_path.clone());
+
+                // Recursively check this library for more libraries
+                parse_library_folders_vdf(&lib_path, libraries, app_libraries);
+            }
         }
     }
 }
 
 // Parse an appmanifest ACF file to extract game information
 fn parse_appmanifest(path: &Path) -> Option<(String, String, String)> {
-    match fs::read_to_string(path) {
-        Ok(content) => {
-            // Use regex to extract the app ID, name, and install directory
-            let re_appid = Regex::new(r#""appid"\s+"(\d+)""#).unwrap();
-            let re_name = Regex::new(r#""name"\s+"([^"]+)""#).unwrap();
-            let re_installdir = Regex::new(r#""installdir"\s+"([^"]+)""#).unwrap();
-
-            if let (Some(app_id_cap), Some(name_cap), Some(dir_cap)) = (
-                re_appid.captures(&content),
-                re_name.captures(&content),
-                re_installdir.captures(&content),
-            ) {
-                let app_id = app_id_cap[1].to_string();
-                let name = name_cap[1].to_string();
-                let install_dir = dir_cap[1].to_string();
-
-                return Some((app_id, name, install_dir));
-            }
-        }
+    let content = match fs::read_to_string(path) {
+        Ok(c) => c,
         Err(e) => {
             error!("Failed to read ACF file {}: {}", path.display(), e);
+            return None;
+        }
+    };
+
+    let root = vdf::parse(&content);
+    let state = root.get("AppState")?;
+
+    let app_id = state.get_str("appid")?.to_string();
+    let name = state.get_str("name")?.to_string();
+    let install_dir = state.get_str("installdir")?.to_string();
+
+    Some((app_id, name, install_dir))
+}
+
+// Resolve the Steam library root (".../steamapps") a game was installed into,
+// so callers can look for sibling appmanifest files
+fn steamapps_dir_for(game_path: &Path) -> Option<PathBuf> {
+    // Games live at <library>/steamapps/common/<installdir>
+    game_path
+        .parent() // .../steamapps/common
+        .and_then(Path::parent) // .../steamapps
+        .map(Path::to_path_buf)
+}
+
+// Check whether an app or DLC is installed locally by looking for its
+// appmanifest in the same Steam library as the base game. This mirrors the
+// ownership concept behind steamworks' `is_app_installed`/`is_dlc_installed`
+// using on-disk manifests instead of the Steamworks SDK.
+pub fn is_app_installed(game_path: &Path, app_id: &str) -> bool {
+    steamapps_dir_for(game_path)
+        .map(|dir| dir.join(format!("appmanifest_{}.acf", app_id)))
+        .map(|manifest| manifest.exists())
+        .unwrap_or(false)
+}
+
+// Extract the substring between a `{` at `start` and its matching `}`,
+// handling nested braces so callers can pull a VDF sub-block out safely
+fn extract_balanced_block(content: &str, start: usize) -> Option<&str> {
+    let bytes = content.as_bytes();
+    if bytes.get(start) != Some(&b'{') {
+        return None;
+    }
+
+    let mut depth = 0i32;
+    for (i, &b) in bytes[start..].iter().enumerate() {
+        match b {
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&content[start + 1..start + i]);
+                }
+            }
+            _ => {}
         }
     }
 
     None
 }
 
+// Parse the `CompatToolMapping` block of a `config.vdf`, returning appid ->
+// compat tool name (appid "0" is the global default). Each per-appid entry
+// is a flat block, so a simple regex over its extracted body is safe even
+// though the surrounding file is deeply nested.
+fn parse_compat_tool_mapping(content: &str) -> HashMap<String, String> {
+    let mut mapping = HashMap::new();
+
+    let key_pos = match content.find("\"CompatToolMapping\"") {
+        Some(p) => p,
+        None => return mapping,
+    };
+
+    let brace_pos = match content[key_pos..].find('{') {
+        Some(offset) => key_pos + offset,
+        None => return mapping,
+    };
+
+    let block = match extract_balanced_block(content, brace_pos) {
+        Some(b) => b,
+        None => return mapping,
+    };
+
+    let re_entry = Regex::new(r#""(\d+)"\s*\{([^{}]*)\}"#).unwrap();
+    let re_name = Regex::new(r#""name"\s+"([^"]*)""#).unwrap();
+
+    for cap in re_entry.captures_iter(block) {
+        if let Some(name_cap) = re_name.captures(&cap[2]) {
+            let name = name_cap[1].to_string();
+            if !name.is_empty() {
+                mapping.insert(cap[1].to_string(), name);
+            }
+        }
+    }
+
+    mapping
+}
+
+// Load and merge the CompatToolMapping from every Steam base path's
+// `config/config.vdf`, so a game's configured Proton/compat tool can be
+// resolved regardless of which base install reported it
+pub fn load_compat_tool_mapping(base_paths: &[PathBuf]) -> HashMap<String, String> {
+    let mut mapping = HashMap::new();
+
+    for base_path in base_paths {
+        let config_vdf = base_path.join("config").join("config.vdf");
+        if let Ok(content) = fs::read_to_string(&config_vdf) {
+            debug!("Parsing compat tool mapping from {}", config_vdf.display());
+            mapping.extend(parse_compat_tool_mapping(&content));
+        }
+    }
+
+    mapping
+}
+
+// Resolve the compat tool for a single appid: its own override, else the
+// global default (appid "0"), else nothing configured
+fn resolve_compat_tool(mapping: &HashMap<String, String>, app_id: &str) -> Option<String> {
+    mapping
+        .get(app_id)
+        .or_else(|| mapping.get("0"))
+        .cloned()
+}
+
+// List compat tools available to the user: official Proton builds installed
+// under a library's `steamapps/common`, plus any custom builds (GE-Proton
+// and friends) under each base path's `compatibilitytools.d`
+pub fn list_compat_tools(base_paths: &[PathBuf], libraries: &[PathBuf]) -> Vec<String> {
+    let mut tools = HashSet::new();
+
+    for library in libraries {
+        let common_dir = library.join("common");
+        if let Ok(entries) = fs::read_dir(&common_dir) {
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if name.to_lowercase().starts_with("proton") {
+                    tools.insert(name);
+                }
+            }
+        }
+    }
+
+    for base_path in base_paths {
+        let custom_dir = base_path.join("compatibilitytools.d");
+        if let Ok(entries) = fs::read_dir(&custom_dir) {
+            for entry in entries.flatten() {
+                if entry.path().is_dir() {
+                    tools.insert(entry.file_name().to_string_lossy().to_string());
+                }
+            }
+        }
+    }
+
+    let mut result: Vec<String> = tools.into_iter().collect();
+    result.sort();
+    result
+}
+
 // Check if a file is a Linux ELF binary
 fn is_elf_binary(path: &Path) -> bool {
     if let Ok(mut file) = fs::File::open(path) {
@@ -405,11 +598,30 @@ fn scan_game_directory(game_path: &Path) -> (bool, Vec<String>) {
     (is_native, steam_api_files)
 }
 
-// Find all installed Steam games from library folders
-pub async fn find_installed_games(steamapps_paths: &[PathBuf]) -> Vec<GameInfo> {
+// Find all installed Steam games from library folders.
+// Unless `force_refresh` is set, games whose appmanifest and install
+// directory haven't changed since the last scan are served from the
+// on-disk scan cache instead of being re-walked.
+pub async fn find_installed_games(steamapps_paths: &[PathBuf], force_refresh: bool) -> Vec<GameInfo> {
     let mut games = Vec::new();
     let seen_ids = Arc::new(tokio::sync::Mutex::new(HashSet::new()));
 
+    if let Err(e) = crate::cache::prune_missing_games() {
+        warn!("Failed to prune stale scan cache entries: {}", e);
+    }
+
+    // A steamapps dir's parent is the Steam root holding config/config.vdf
+    let steam_roots: Vec<PathBuf> = steamapps_paths
+        .iter()
+        .filter_map(|p| p.parent().map(Path::to_path_buf))
+        .collect();
+    let compat_tool_mapping = Arc::new(load_compat_tool_mapping(&steam_roots));
+
+    // Appid -> owning library, read from each library's libraryfolders.vdf
+    // `apps` block, used below as a sanity check against directory scanning
+    let (_, app_libraries) = find_steam_libraries_and_app_map(&steam_roots);
+    let app_libraries = Arc::new(app_libraries);
+
     // IDs to skip (tools, redistributables, etc.)
     let skip_ids = Arc::new(
         [
@@ -483,6 +695,8 @@ pub async fn find_installed_games(steamapps_paths: &[PathBuf]) -> Vec<GameInfo>
         let seen_ids = Arc::clone(&seen_ids);
         let semaphore = Arc::clone(&semaphore);
         let skip_ids = Arc::clone(&skip_ids);
+        let compat_tool_mapping = Arc::clone(&compat_tool_mapping);
+        let app_libraries = Arc::clone(&app_libraries);
 
         // Create a new task
         let handle = tokio::spawn(async move {
@@ -520,6 +734,34 @@ pub async fn find_installed_games(steamapps_paths: &[PathBuf]) -> Vec<GameInfo>
                     return;
                 }
 
+                // Cross-check against libraryfolders.vdf's `apps` ownership
+                // map; a mismatch usually just means a stale/missing entry,
+                // not a problem, so it's only worth a debug note
+                if let Some(owning_library) = app_libraries.get(&id) {
+                    if owning_library != &steamapps_dir {
+                        debug!(
+                            "Appmanifest for {} found under {} but libraryfolders.vdf lists {}",
+                            id,
+                            steamapps_dir.display(),
+                            owning_library.display()
+                        );
+                    }
+                }
+
+                // Reuse the cached scan result if the appmanifest and game
+                // directory fingerprints haven't changed since last time
+                if !force_refresh {
+                    if let Some(cached) =
+                        crate::cache::get_cached_scanned_game(&id, &path, &game_path)
+                    {
+                        debug!("Using cached scan result for: {} ({})", cached.title, id);
+                        if tx.send(cached).await.is_err() {
+                            error!("Failed to send cached game info through channel");
+                        }
+                        return;
+                    }
+                }
+
                 // Scan the game directory to determine platform and find Steam API DLLs
                 info!("Scanning game: {} at {}", name, game_path.display());
 
@@ -537,16 +779,25 @@ pub async fn find_installed_games(steamapps_paths: &[PathBuf]) -> Vec<GameInfo>
                 };
 
                 // Create the game info
+                let compat_tool = resolve_compat_tool(&compat_tool_mapping, &id);
                 let game_info = GameInfo {
-                    id,
+                    id: id.clone(),
                     title: name,
-                    path: game_path,
+                    path: game_path.clone(),
                     native: is_native,
                     api_files,
                     cream_installed,
                     smoke_installed,
+                    source: GameSource::Steam,
+                    compat_tool,
                 };
 
+                if let Err(e) =
+                    crate::cache::store_scanned_game(&id, &game_info, &path, &game_path)
+                {
+                    warn!("Failed to persist scan cache for {}: {}", id, e);
+                }
+
                 // Send the game info through the channel
                 if tx.send(game_info).await.is_err() {
                     error!("Failed to send game info through channel");
@@ -604,3 +855,176 @@ pub async fn find_installed_games(steamapps_paths: &[PathBuf]) -> Vec<GameInfo>
     info!("Found {} installed games", games.len());
     games
 }
+
+// Discover games installed via Heroic (GOG) and Legendary (Epic), which live
+// entirely outside Steam's appmanifest/ACF world
+pub async fn find_launcher_games() -> Vec<GameInfo> {
+    let mut games = Vec::new();
+
+    let home = match std::env::var("HOME") {
+        Ok(h) => h,
+        Err(_) => return games,
+    };
+
+    let heroic_dir = PathBuf::from(&home).join(".config/heroic");
+    if heroic_dir.exists() {
+        games.extend(find_heroic_gog_games(&heroic_dir));
+    }
+
+    let legendary_dir = PathBuf::from(&home).join(".config/legendary");
+    if legendary_dir.exists() {
+        games.extend(find_legendary_games(&legendary_dir));
+    }
+
+    info!("Found {} games from non-Steam launchers", games.len());
+    games
+}
+
+// Read Heroic's GOG store manifests: `installed.json` for what's on disk and
+// `library.json` to resolve the opaque appName to a human title
+fn find_heroic_gog_games(heroic_dir: &Path) -> Vec<GameInfo> {
+    let store_dir = heroic_dir.join("gog_store");
+
+    let installed: serde_json::Value = match fs::read_to_string(store_dir.join("installed.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+    {
+        Some(v) => v,
+        None => return Vec::new(),
+    };
+
+    let titles: HashMap<String, String> = fs::read_to_string(store_dir.join("library.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|v| v.get("games").and_then(|g| g.as_array().cloned()))
+        .map(|entries| {
+            entries
+                .iter()
+                .filter_map(|entry| {
+                    let app_name = entry
+                        .get("app_name")
+                        .or_else(|| entry.get("appName"))
+                        .and_then(|v| v.as_str())?;
+                    let title = entry.get("title").and_then(|v| v.as_str())?;
+                    Some((app_name.to_string(), title.to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let entries = installed
+        .get("installed")
+        .and_then(|v| v.as_array().cloned())
+        .or_else(|| installed.as_array().cloned())
+        .unwrap_or_default();
+
+    let mut games = Vec::new();
+    for entry in &entries {
+        let app_name = match entry.get("appName").and_then(|v| v.as_str()) {
+            Some(v) => v.to_string(),
+            None => continue,
+        };
+
+        let install_path = match entry.get("install_path").and_then(|v| v.as_str()) {
+            Some(v) => PathBuf::from(v),
+            None => continue,
+        };
+
+        if !install_path.exists() {
+            debug!(
+                "Heroic GOG game {} install path missing, skipping: {}",
+                app_name,
+                install_path.display()
+            );
+            continue;
+        }
+
+        let title = titles
+            .get(&app_name)
+            .cloned()
+            .unwrap_or_else(|| app_name.clone());
+
+        let (is_native, api_files) = scan_game_directory(&install_path);
+        let cream_installed = check_creamlinux_installed(&install_path);
+        let smoke_installed = if !is_native && !api_files.is_empty() {
+            check_smokeapi_installed(&install_path, &api_files)
+        } else {
+            false
+        };
+
+        info!("Found Heroic (GOG) game: {} ({})", title, app_name);
+
+        games.push(GameInfo {
+            id: app_name,
+            title,
+            path: install_path,
+            native: is_native,
+            api_files,
+            cream_installed,
+            smoke_installed,
+            source: GameSource::HeroicGog,
+            compat_tool: None,
+        });
+    }
+
+    games
+}
+
+// Read Legendary's `installed.json` (also used by Heroic for Epic titles) -
+// a map of appName to its installed metadata
+fn find_legendary_games(legendary_dir: &Path) -> Vec<GameInfo> {
+    let installed = match fs::read_to_string(legendary_dir.join("installed.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+    {
+        Some(serde_json::Value::Object(map)) => map,
+        _ => return Vec::new(),
+    };
+
+    let mut games = Vec::new();
+    for (app_name, entry) in &installed {
+        let install_path = match entry.get("install_path").and_then(|v| v.as_str()) {
+            Some(v) => PathBuf::from(v),
+            None => continue,
+        };
+
+        if !install_path.exists() {
+            debug!(
+                "Legendary game {} install path missing, skipping: {}",
+                app_name,
+                install_path.display()
+            );
+            continue;
+        }
+
+        let title = entry
+            .get("title")
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| app_name.clone());
+
+        let (is_native, api_files) = scan_game_directory(&install_path);
+        let cream_installed = check_creamlinux_installed(&install_path);
+        let smoke_installed = if !is_native && !api_files.is_empty() {
+            check_smokeapi_installed(&install_path, &api_files)
+        } else {
+            false
+        };
+
+        info!("Found Legendary (Epic) game: {} ({})", title, app_name);
+
+        games.push(GameInfo {
+            id: app_name.clone(),
+            title,
+            path: install_path,
+            native: is_native,
+            api_files,
+            cream_installed,
+            smoke_installed,
+            source: GameSource::HeroicEpic,
+            compat_tool: None,
+        });
+    }
+
+    games
+}
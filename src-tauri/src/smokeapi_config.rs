@@ -1,8 +1,60 @@
 use log::{info, warn};
-use serde::{Deserialize, Serialize};
+use parking_lot::Mutex;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
 use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use thiserror::Error;
+
+// Structured error type for reading/writing a game's SmokeAPI.config.json,
+// mirroring `InstallerError`'s shape so callers can branch on `code()`.
+#[derive(Debug, Error)]
+pub enum SmokeApiConfigError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error("failed to parse SmokeAPI config: {0}")]
+    Parse(#[from] serde_json::Error),
+
+    #[error("{0}")]
+    Other(String),
+}
+
+impl SmokeApiConfigError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            SmokeApiConfigError::Io(_) => "io",
+            SmokeApiConfigError::Parse(_) => "parse",
+            SmokeApiConfigError::Other(_) => "other",
+        }
+    }
+}
+
+impl From<SmokeApiConfigError> for String {
+    fn from(err: SmokeApiConfigError) -> Self {
+        err.to_string()
+    }
+}
+
+impl From<String> for SmokeApiConfigError {
+    fn from(s: String) -> Self {
+        SmokeApiConfigError::Other(s)
+    }
+}
+
+impl Serialize for SmokeApiConfigError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("SmokeApiConfigError", 2)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SmokeAPIConfig {
@@ -37,9 +89,31 @@ impl Default for SmokeAPIConfig {
     }
 }
 
-// Read SmokeAPI config from a game directory
-// Returns None if the config doesn't exist
-pub fn read_config(game_path: &str) -> Result<Option<SmokeAPIConfig>, String> {
+// In-memory cache of parsed configs, keyed by their resolved config path, so
+// repeated reads during a UI edit session don't keep re-walking the game
+// directory and re-parsing JSON from disk.
+static CONFIG_CACHE: OnceLock<Mutex<HashMap<PathBuf, SmokeAPIConfig>>> = OnceLock::new();
+
+fn cache() -> &'static Mutex<HashMap<PathBuf, SmokeAPIConfig>> {
+    CONFIG_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+// Get the SmokeAPI config for a game directory, serving a cached clone if one
+// is available and falling back to `get_raw` otherwise.
+// Returns None if the config doesn't exist.
+pub fn get(game_path: &str) -> Result<Option<SmokeAPIConfig>, SmokeApiConfigError> {
+    let config_path = find_smokeapi_config_path(game_path)?;
+
+    if let Some(config) = cache().lock().get(&config_path) {
+        return Ok(Some(config.clone()));
+    }
+
+    get_raw(game_path)
+}
+
+// Read SmokeAPI config from a game directory, always hitting disk and
+// refreshing the cached entry. Returns None if the config doesn't exist.
+pub fn get_raw(game_path: &str) -> Result<Option<SmokeAPIConfig>, SmokeApiConfigError> {
     info!("Reading SmokeAPI config from: {}", game_path);
 
     // Find the SmokeAPI DLL location in the game directory
@@ -47,55 +121,57 @@ pub fn read_config(game_path: &str) -> Result<Option<SmokeAPIConfig>, String> {
 
     if !config_path.exists() {
         info!("No SmokeAPI config found at: {}", config_path.display());
+        cache().lock().remove(&config_path);
         return Ok(None);
     }
 
-    let content = fs::read_to_string(&config_path)
-        .map_err(|e| format!("Failed to read SmokeAPI config: {}", e))?;
+    let content = fs::read_to_string(&config_path)?;
 
-    let config: SmokeAPIConfig = serde_json::from_str(&content)
-        .map_err(|e| format!("Failed to parse SmokeAPI config: {}", e))?;
+    let config: SmokeAPIConfig = serde_json::from_str(&content)?;
+
+    cache().lock().insert(config_path.clone(), config.clone());
 
     info!("Successfully read SmokeAPI config");
     Ok(Some(config))
 }
 
 // Write SmokeAPI config to a game directory
-pub fn write_config(game_path: &str, config: &SmokeAPIConfig) -> Result<(), String> {
+pub fn write_config(game_path: &str, config: &SmokeAPIConfig) -> Result<(), SmokeApiConfigError> {
     info!("Writing SmokeAPI config to: {}", game_path);
 
     let config_path = find_smokeapi_config_path(game_path)?;
 
-    let content = serde_json::to_string_pretty(config)
-        .map_err(|e| format!("Failed to serialize SmokeAPI config: {}", e))?;
+    let content = serde_json::to_string_pretty(config)?;
+
+    fs::write(&config_path, content)?;
 
-    fs::write(&config_path, content)
-        .map_err(|e| format!("Failed to write SmokeAPI config: {}", e))?;
+    cache().lock().insert(config_path.clone(), config.clone());
 
     info!("Successfully wrote SmokeAPI config to: {}", config_path.display());
     Ok(())
 }
 
 // Delete SmokeAPI config from a game directory
-pub fn delete_config(game_path: &str) -> Result<(), String> {
+pub fn delete_config(game_path: &str) -> Result<(), SmokeApiConfigError> {
     info!("Deleting SmokeAPI config from: {}", game_path);
 
     let config_path = find_smokeapi_config_path(game_path)?;
 
     if config_path.exists() {
-        fs::remove_file(&config_path)
-            .map_err(|e| format!("Failed to delete SmokeAPI config: {}", e))?;
+        fs::remove_file(&config_path)?;
         info!("Successfully deleted SmokeAPI config");
     } else {
         info!("No SmokeAPI config to delete");
     }
 
+    cache().lock().remove(&config_path);
+
     Ok(())
 }
 
 // Find the path where SmokeAPI.config.json should be located
 // This is in the same directory as the SmokeAPI DLL files
-fn find_smokeapi_config_path(game_path: &str) -> Result<std::path::PathBuf, String> {
+fn find_smokeapi_config_path(game_path: &str) -> Result<std::path::PathBuf, SmokeApiConfigError> {
     let game_path_obj = Path::new(game_path);
 
     // Search for steam_api*.dll files with _o.dll backups (indicating SmokeAPI installation)
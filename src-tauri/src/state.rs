@@ -0,0 +1,137 @@
+// Persisted installed-game registry.
+//
+// This used to be a natural fit for `config::Config`, but it changes on
+// every scan/install/uninstall while `Config` only changes when the user
+// edits a setting — bundling the two meant an unrelated game scan could
+// rewrite the user's preferences file. Splitting it into its own data file
+// keeps `Config` purely user-authored.
+
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum StateError {
+    #[error("failed to determine data directory: {0}")]
+    BaseDirectories(String),
+
+    #[error("failed to parse state file: {0}")]
+    Parse(String),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+// Tauri commands still return `Result<_, String>`, so callers can propagate
+// a `StateError` with `?` without an explicit `.map_err`.
+impl From<StateError> for String {
+    fn from(err: StateError) -> Self {
+        err.to_string()
+    }
+}
+
+// A single game's unlocker install status, as last observed by a scan
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GameInstallState {
+    pub cream_installed: bool,
+    pub smoke_installed: bool,
+}
+
+// Registry of installed-unlocker state, keyed by game id
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AppData {
+    pub games: HashMap<String, GameInstallState>,
+}
+
+// Get the data directory path (XDG data home, e.g. ~/.local/share/creamlinux)
+pub(crate) fn get_data_dir() -> Result<PathBuf, StateError> {
+    let xdg_dirs = xdg::BaseDirectories::with_prefix("creamlinux")
+        .map_err(|e| StateError::BaseDirectories(e.to_string()))?;
+
+    Ok(xdg_dirs.get_data_home())
+}
+
+fn state_path() -> Result<PathBuf, StateError> {
+    let dir = get_data_dir()?;
+
+    if !dir.exists() {
+        fs::create_dir_all(&dir)?;
+        info!("Created data directory at {:?}", dir);
+    }
+
+    Ok(dir.join("state.json"))
+}
+
+// Load the installed-game registry, defaulting to empty if it doesn't exist yet
+pub fn load_state() -> Result<AppData, StateError> {
+    let path = state_path()?;
+
+    if !path.exists() {
+        return Ok(AppData::default());
+    }
+
+    let content = fs::read_to_string(&path)?;
+    let data: AppData =
+        serde_json::from_str(&content).map_err(|e| StateError::Parse(e.to_string()))?;
+
+    info!("Loaded installed-game registry from {:?}", path);
+    Ok(data)
+}
+
+// Save the installed-game registry
+pub fn save_state(data: &AppData) -> Result<(), StateError> {
+    let path = state_path()?;
+
+    let content =
+        serde_json::to_string_pretty(data).map_err(|e| StateError::Parse(e.to_string()))?;
+    fs::write(&path, content)?;
+
+    info!("Saved installed-game registry to {:?}", path);
+    Ok(())
+}
+
+// Replace the registry wholesale with a fresh scan's results
+pub fn save_scan_results<'a>(
+    games: impl IntoIterator<Item = (&'a str, GameInstallState)>,
+) -> Result<(), StateError> {
+    let data = AppData {
+        games: games
+            .into_iter()
+            .map(|(id, state)| (id.to_string(), state))
+            .collect(),
+    };
+
+    save_state(&data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_app_data_default_is_empty() {
+        let data = AppData::default();
+        assert!(data.games.is_empty());
+    }
+
+    #[test]
+    fn test_app_data_roundtrip() {
+        let mut data = AppData::default();
+        data.games.insert(
+            "123".to_string(),
+            GameInstallState {
+                cream_installed: true,
+                smoke_installed: false,
+            },
+        );
+
+        let json = serde_json::to_string(&data).unwrap();
+        let parsed: AppData = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed.games["123"].cream_installed, true);
+        assert_eq!(parsed.games["123"].smoke_installed, false);
+    }
+}
@@ -1,4 +1,4 @@
-use super::Unlocker;
+use super::{stream_download_body, ProgressSender, Unlocker, UnlockerError, VerifiedVersion};
 use async_trait::async_trait;
 use log::{info, warn};
 use reqwest;
@@ -9,58 +9,254 @@ use std::time::Duration;
 use tempfile::tempdir;
 use zip::ZipArchive;
 
+pub(crate) const CREAMLINUX_REPO: &str = "anticitizn/creamlinux";
+
+// The CreamLinux binaries a working installation needs present in the game
+// directory, mirroring the list `uninstall_from_game` removes.
+pub(crate) const CREAMLINUX_BINARIES: &[&str] =
+    &["cream.sh", "lib32Creamlinux.so", "lib64Creamlinux.so"];
+
+// What state a game's CreamLinux installation is in, so the UI can drive
+// install/repair/update actions deterministically instead of inferring them
+// from scattered file-existence checks.
+#[derive(serde::Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "state")]
+pub enum InstallState {
+    NotInstalled,
+    ConfigMissing,
+    BinariesMissing,
+    UpdateAvailable { current: String, latest: String },
+    Ready,
+}
+
+// Determine the CreamLinux install state for a game directory by cross
+// referencing the on-disk binaries/config against the version manifest.
+pub fn get_install_state(game_path: &str) -> Result<InstallState, String> {
+    let game_path_obj = Path::new(game_path);
+    let has_binaries = CREAMLINUX_BINARIES
+        .iter()
+        .all(|name| game_path_obj.join(name).exists());
+    let has_config = game_path_obj.join("cream_api.ini").exists();
+
+    if !has_binaries && !has_config {
+        return Ok(InstallState::NotInstalled);
+    }
+
+    if !has_config {
+        return Ok(InstallState::ConfigMissing);
+    }
+
+    if !has_binaries {
+        return Ok(InstallState::BinariesMissing);
+    }
+
+    let manifest = crate::cache::read_manifest(game_path)?;
+    let latest = crate::cache::read_versions()?.creamlinux.active;
+
+    if let Some(current) = manifest.creamlinux_version {
+        if current != latest {
+            return Ok(InstallState::UpdateAvailable { current, latest });
+        }
+    }
+
+    Ok(InstallState::Ready)
+}
+
 pub struct CreamLinux;
 
+impl CreamLinux {
+    // Verify an already-downloaded archive's digest against the release's
+    // checksums asset (if it published one) before it's extracted anywhere.
+    // A release without one logs a warning and is allowed through.
+    // Returns the SHA-256 the archive was verified against, so the caller
+    // can persist it alongside the cached version — `None` when the release
+    // published nothing to verify against at all.
+    async fn verify_download_integrity(
+        client: &reqwest::Client,
+        version: &str,
+        zip_name: &str,
+        actual: &str,
+    ) -> Result<Option<String>, UnlockerError> {
+        let release_url = format!(
+            "https://api.github.com/repos/{}/releases/tags/{}",
+            CREAMLINUX_REPO, version
+        );
+
+        let release_info: serde_json::Value = client
+            .get(&release_url)
+            .header("User-Agent", "CreamLinux-Installer")
+            .timeout(Duration::from_secs(10))
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        let assets = release_info
+            .get("assets")
+            .and_then(|a| a.as_array())
+            .cloned()
+            .unwrap_or_default();
+
+        // Prefer the digest GitHub publishes on the asset itself - it's
+        // always available for an asset that has one, without needing a
+        // second request for a separate checksums file.
+        if let Some(expected) = crate::checksum::find_asset_digest_sha256(&assets, zip_name) {
+            if !actual.eq_ignore_ascii_case(&expected) {
+                return Err(UnlockerError::ChecksumMismatch(format!(
+                    "{} — refusing to extract a possibly corrupted or tampered archive",
+                    zip_name
+                )));
+            }
+            info!("Verified CreamLinux {} archive digest", version);
+            return Ok(Some(actual.to_string()));
+        }
+
+        let Some(checksums_url) = crate::checksum::find_checksums_asset_url(&assets) else {
+            warn!(
+                "CreamLinux {} release does not publish a checksums file; skipping integrity verification",
+                version
+            );
+            return Ok(None);
+        };
+
+        let checksums = client
+            .get(&checksums_url)
+            .timeout(Duration::from_secs(10))
+            .send()
+            .await?
+            .text()
+            .await?;
+
+        match crate::checksum::verify_digest_against_checksums(actual, &checksums, zip_name) {
+            Some(true) => {
+                info!("Verified CreamLinux {} archive checksum", version);
+                Ok(Some(actual.to_string()))
+            }
+            Some(false) => Err(UnlockerError::ChecksumMismatch(format!(
+                "{} — refusing to extract a possibly corrupted or tampered archive",
+                zip_name
+            ))),
+            None => {
+                warn!(
+                    "No checksum entry for {} in CreamLinux {} release; skipping integrity verification",
+                    zip_name, version
+                );
+                Ok(None)
+            }
+        }
+    }
+
+    // Write each cached file into the game directory through `transaction`
+    // so every copy is tracked for rollback/uninstall, making .sh files
+    // executable as they land.
+    fn stage_binaries(
+        transaction: &mut crate::installer::InstallTransaction,
+        game_path: &Path,
+        cached_files: &[std::path::PathBuf],
+    ) -> Result<(), UnlockerError> {
+        for file in cached_files {
+            let file_name = file.file_name().ok_or_else(|| {
+                UnlockerError::Other(format!("Failed to get filename from: {}", file.display()))
+            })?;
+
+            let dest_path = game_path.join(file_name);
+            let content = fs::read(file)?;
+            transaction.write_file(&dest_path, &content)?;
+
+            if file_name.to_string_lossy().ends_with(".sh") {
+                #[cfg(unix)]
+                {
+                    use std::os::unix::fs::PermissionsExt;
+                    let mut perms = fs::metadata(&dest_path)?.permissions();
+                    perms.set_mode(0o755);
+                    fs::set_permissions(&dest_path, perms)?;
+                }
+            }
+
+            info!("Installed: {}", dest_path.display());
+        }
+
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl Unlocker for CreamLinux {
-    async fn get_latest_version() -> Result<String, String> {
+    async fn get_latest_version() -> Result<String, UnlockerError> {
         info!("Fetching latest CreamLinux version...");
 
         let client = reqwest::Client::new();
-        
+
         // Fetch the latest release from GitHub API
-        let api_url = "https://api.github.com/repos/anticitizn/creamlinux/releases/latest";
-        
+        let api_url = format!(
+            "https://api.github.com/repos/{}/releases/latest",
+            CREAMLINUX_REPO
+        );
+
         let response = client
-            .get(api_url)
+            .get(&api_url)
             .header("User-Agent", "CreamLinux-Installer")
             .timeout(Duration::from_secs(10))
             .send()
-            .await
-            .map_err(|e| format!("Failed to fetch CreamLinux releases: {}", e))?;
+            .await?;
 
         if !response.status().is_success() {
-            return Err(format!(
+            return Err(UnlockerError::Http(format!(
                 "Failed to fetch CreamLinux releases: HTTP {}",
                 response.status()
-            ));
+            )));
         }
 
-        let release_info: serde_json::Value = response
-            .json()
-            .await
-            .map_err(|e| format!("Failed to parse release info: {}", e))?;
+        let release_info: serde_json::Value = response.json().await?;
 
         let version = release_info
             .get("tag_name")
             .and_then(|v| v.as_str())
-            .ok_or_else(|| "Failed to extract version from release info".to_string())?
+            .ok_or_else(|| UnlockerError::Other("Failed to extract version from release info".to_string()))?
             .to_string();
 
         info!("Latest CreamLinux version: {}", version);
         Ok(version)
     }
 
-    async fn download_to_cache() -> Result<String, String> {
-        let version = Self::get_latest_version().await?;
+    async fn verify_release_exists(tag: &str) -> Result<(), UnlockerError> {
+        let client = reqwest::Client::new();
+        let release_url = format!(
+            "https://api.github.com/repos/{}/releases/tags/{}",
+            CREAMLINUX_REPO, tag
+        );
+
+        let response = client
+            .get(&release_url)
+            .header("User-Agent", "CreamLinux-Installer")
+            .timeout(Duration::from_secs(10))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(UnlockerError::VersionNotFound(format!(
+                "CreamLinux release {} does not exist",
+                tag
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn download_to_cache(
+        version: &str,
+        progress: Option<ProgressSender>,
+    ) -> Result<VerifiedVersion, UnlockerError> {
+        let version = version.to_string();
         info!("Downloading CreamLinux version {}...", version);
 
         let client = reqwest::Client::new();
-        
+
         // Construct the download URL using the version
+        let zip_name = "creamlinux.zip";
         let download_url = format!(
-            "https://github.com/anticitizn/creamlinux/releases/download/{}/creamlinux.zip",
-            version
+            "https://github.com/{}/releases/download/{}/{}",
+            CREAMLINUX_REPO, version, zip_name
         );
 
         // Download the zip
@@ -68,36 +264,38 @@ impl Unlocker for CreamLinux {
             .get(&download_url)
             .timeout(Duration::from_secs(30))
             .send()
-            .await
-            .map_err(|e| format!("Failed to download CreamLinux: {}", e))?;
+            .await?;
 
         if !response.status().is_success() {
-            return Err(format!(
+            return Err(UnlockerError::Http(format!(
                 "Failed to download CreamLinux: HTTP {}",
                 response.status()
-            ));
+            )));
         }
 
-        // Save to temporary file
-        let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+        // Stream straight to a temporary file instead of buffering the
+        // whole archive in memory.
+        let temp_dir = tempdir()?;
         let zip_path = temp_dir.path().join("creamlinux.zip");
-        let content = response
-            .bytes()
-            .await
-            .map_err(|e| format!("Failed to read response bytes: {}", e))?;
-        fs::write(&zip_path, &content).map_err(|e| format!("Failed to write zip file: {}", e))?;
+        let digest =
+            stream_download_body(response, "CreamLinux", version.clone(), &zip_path, progress)
+                .await?;
+
+        info!("Verifying download integrity for CreamLinux {}...", version);
+        let sha256 = Self::verify_download_integrity(&client, &version, zip_name, &digest).await?;
 
         // Extract to cache directory
-        let version_dir = crate::cache::get_creamlinux_version_dir(&version)?;
-        let file = fs::File::open(&zip_path).map_err(|e| format!("Failed to open zip: {}", e))?;
+        let version_dir = crate::cache::get_creamlinux_version_dir(&version)
+            .map_err(UnlockerError::Other)?;
+        let file = fs::File::open(&zip_path)?;
         let mut archive =
-            ZipArchive::new(file).map_err(|e| format!("Failed to read zip archive: {}", e))?;
+            ZipArchive::new(file).map_err(|e| UnlockerError::ZipExtraction(e.to_string()))?;
 
         // Extract all files
         for i in 0..archive.len() {
             let mut file = archive
                 .by_index(i)
-                .map_err(|e| format!("Failed to access zip entry: {}", e))?;
+                .map_err(|e| UnlockerError::ZipExtraction(e.to_string()))?;
 
             let file_name = file.name().to_string(); // Clone the name early
 
@@ -112,22 +310,18 @@ impl Unlocker for CreamLinux {
                     .unwrap_or_else(|| std::ffi::OsStr::new(&file_name)),
             );
 
-            let mut outfile = fs::File::create(&output_path)
-                .map_err(|e| format!("Failed to create output file: {}", e))?;
+            let mut outfile = fs::File::create(&output_path)?;
             io::copy(&mut file, &mut outfile)
-                .map_err(|e| format!("Failed to extract file: {}", e))?;
+                .map_err(|e| UnlockerError::ZipExtraction(e.to_string()))?;
 
             // Make .sh files executable
             if file_name.ends_with(".sh") {
                 #[cfg(unix)]
                 {
                     use std::os::unix::fs::PermissionsExt;
-                    let mut perms = fs::metadata(&output_path)
-                        .map_err(|e| format!("Failed to get file metadata: {}", e))?
-                        .permissions();
+                    let mut perms = fs::metadata(&output_path)?.permissions();
                     perms.set_mode(0o755);
-                    fs::set_permissions(&output_path, perms)
-                        .map_err(|e| format!("Failed to set permissions: {}", e))?;
+                    fs::set_permissions(&output_path, perms)?;
                 }
             }
 
@@ -138,46 +332,40 @@ impl Unlocker for CreamLinux {
             "CreamLinux version {} downloaded to cache successfully",
             version
         );
-        Ok(version)
+        Ok(VerifiedVersion { version, sha256 })
     }
 
-    async fn install_to_game(game_path: &str, _game_id: &str) -> Result<(), String> {
+    async fn install_to_game(game_path: &str, _game_id: &str) -> Result<(), UnlockerError> {
         info!("Installing CreamLinux to {}", game_path);
 
         // Get the cached CreamLinux files
-        let cached_files = crate::cache::list_creamlinux_files()?;
+        let cached_files = crate::cache::list_creamlinux_files().map_err(UnlockerError::Other)?;
         if cached_files.is_empty() {
-            return Err("No CreamLinux files found in cache".to_string());
+            return Err(UnlockerError::NotCached {
+                what: "CreamLinux files",
+            });
         }
 
         let game_path_obj = Path::new(game_path);
 
-        // Copy all files to the game directory
-        for file in &cached_files {
-            let file_name = file.file_name().ok_or_else(|| {
-                format!("Failed to get filename from: {}", file.display())
-            })?;
-
-            let dest_path = game_path_obj.join(file_name);
+        // Record every file written (backing up anything it clobbers) so a
+        // failure partway through can be unwound instead of leaving the game
+        // directory half-patched, and so uninstall later knows exactly what
+        // this install touched.
+        let mut transaction = crate::installer::InstallTransaction::new(game_path, "creamlinux");
 
-            fs::copy(file, &dest_path)
-                .map_err(|e| format!("Failed to copy {} to game directory: {}", file_name.to_string_lossy(), e))?;
+        let result = Self::stage_binaries(&mut transaction, game_path_obj, &cached_files);
 
-            // Make .sh files executable
-            if file_name.to_string_lossy().ends_with(".sh") {
-                #[cfg(unix)]
-                {
-                    use std::os::unix::fs::PermissionsExt;
-                    let mut perms = fs::metadata(&dest_path)
-                        .map_err(|e| format!("Failed to get file metadata: {}", e))?
-                        .permissions();
-                    perms.set_mode(0o755);
-                    fs::set_permissions(&dest_path, perms)
-                        .map_err(|e| format!("Failed to set permissions: {}", e))?;
-                }
+        match result {
+            Ok(()) => {
+                transaction
+                    .commit()
+                    .map_err(UnlockerError::Other)?;
+            }
+            Err(e) => {
+                transaction.rollback();
+                return Err(e);
             }
-
-            info!("Installed: {}", dest_path.display());
         }
 
         // Note: cream_api.ini is managed separately by dlc_manager
@@ -187,12 +375,31 @@ impl Unlocker for CreamLinux {
         Ok(())
     }
 
-    async fn uninstall_from_game(game_path: &str, _game_id: &str) -> Result<(), String> {
+    async fn uninstall_from_game(game_path: &str, _game_id: &str) -> Result<(), UnlockerError> {
         info!("Uninstalling CreamLinux from: {}", game_path);
 
+        // Prefer the install manifest recorded by `install_to_game` - it
+        // reflects exactly what that install wrote, unlike a static list
+        // that drifts as upstream archives add/rename files.
+        match crate::installer::uninstall_via_manifest(game_path, "creamlinux") {
+            Ok(true) => {
+                info!("CreamLinux uninstallation completed for: {}", game_path);
+                return Ok(());
+            }
+            Ok(false) => {
+                info!(
+                    "No install manifest for {} (pre-dates manifest tracking), falling back to the static file list",
+                    game_path
+                );
+            }
+            Err(e) => {
+                warn!("Failed to uninstall CreamLinux via manifest: {}", e);
+            }
+        }
+
         let game_path_obj = Path::new(game_path);
 
-        // List of CreamLinux files to remove
+        // Legacy fallback for installs made before manifest tracking existed
         let files_to_remove = vec![
             "cream.sh",
             "lib32Creamlinux.so",
@@ -222,4 +429,4 @@ impl Unlocker for CreamLinux {
     fn name() -> &'static str {
         "CreamLinux"
     }
-}
\ No newline at end of file
+}
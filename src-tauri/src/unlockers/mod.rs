@@ -1,27 +1,223 @@
-mod creamlinux;
-mod smokeapi;
+pub(crate) mod creamlinux;
+pub(crate) mod smokeapi;
 
-pub use creamlinux::CreamLinux;
+pub use creamlinux::{get_install_state, CreamLinux, InstallState};
 pub use smokeapi::SmokeAPI;
 
 use async_trait::async_trait;
+use futures::stream::StreamExt;
+use log::info;
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::path::Path;
+use thiserror::Error;
+use tokio::sync::mpsc::UnboundedSender;
+
+// Structured error type for the unlocker layer (downloading, verifying and
+// installing CreamLinux/SmokeAPI), mirroring `InstallerError`'s shape so the
+// frontend can branch on `code()` instead of matching on English text.
+#[derive(Debug, Error)]
+pub enum UnlockerError {
+    #[error("network request failed: {0}")]
+    Network(#[from] reqwest::Error),
+
+    #[error("request failed: {0}")]
+    Http(String),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error("failed to extract archive: {0}")]
+    ZipExtraction(String),
+
+    #[error("failed to verify release {0}")]
+    VersionNotFound(String),
+
+    #[error("checksum mismatch: {0}")]
+    ChecksumMismatch(String),
+
+    #[error("no matching {arch}-bit DLL found in cache")]
+    MissingArchDll { arch: &'static str },
+
+    #[error("backup file missing: {path}")]
+    BackupMissing { path: String },
+
+    #[error("{what} not found in cache")]
+    NotCached { what: &'static str },
+
+    #[error("{0}")]
+    Other(String),
+}
+
+impl UnlockerError {
+    // A stable, machine-readable identifier the frontend can match on
+    pub fn code(&self) -> &'static str {
+        match self {
+            UnlockerError::Network(_) => "network",
+            UnlockerError::Http(_) => "http",
+            UnlockerError::Io(_) => "io",
+            UnlockerError::ZipExtraction(_) => "zip_extraction",
+            UnlockerError::VersionNotFound(_) => "version_not_found",
+            UnlockerError::ChecksumMismatch(_) => "checksum_mismatch",
+            UnlockerError::MissingArchDll { .. } => "missing_arch_dll",
+            UnlockerError::BackupMissing { .. } => "backup_missing",
+            UnlockerError::NotCached { .. } => "not_cached",
+            UnlockerError::Other(_) => "other",
+        }
+    }
+}
+
+// Tauri commands and the installer layer still pass `String` errors around,
+// so callers can propagate an `UnlockerError` with `?` without an explicit
+// `.map_err`.
+impl From<UnlockerError> for String {
+    fn from(err: UnlockerError) -> Self {
+        err.to_string()
+    }
+}
+
+impl From<String> for UnlockerError {
+    fn from(s: String) -> Self {
+        UnlockerError::Other(s)
+    }
+}
+
+// Serialized as `{ "code": "...", "message": "..." }` for the frontend
+impl Serialize for UnlockerError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("UnlockerError", 2)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
+
+// A single progress update emitted while streaming a download to the cache
+#[derive(Debug, Clone)]
+pub struct DownloadProgress {
+    pub component: &'static str,
+    pub version: String,
+    pub downloaded_bytes: u64,
+    // `None` when the response didn't send a `Content-Length` header
+    pub total_bytes: Option<u64>,
+    // Human-readable "X of Y MB" (or just "X MB" without a known total),
+    // so the frontend doesn't have to reimplement byte formatting
+    pub message: String,
+}
+
+fn format_mb(bytes: u64) -> String {
+    format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+}
+
+fn format_download_message(downloaded_bytes: u64, total_bytes: Option<u64>) -> String {
+    match total_bytes {
+        Some(total) => format!("{} of {}", format_mb(downloaded_bytes), format_mb(total)),
+        None => format_mb(downloaded_bytes),
+    }
+}
+
+// Channel a caller can supply to `download_to_cache` to receive progress
+// updates as the archive streams in, instead of blocking until it's done
+pub type ProgressSender = UnboundedSender<DownloadProgress>;
+
+// What `download_to_cache` actually fetched: the resolved version, plus the
+// SHA-256 it was verified against (`None` when the release published no
+// digest/checksums asset to verify against at all). Carrying this out of
+// the download lets the cache layer record what a version was last verified
+// with instead of just trusting the extracted files forever.
+#[derive(Debug, Clone)]
+pub struct VerifiedVersion {
+    pub version: String,
+    pub sha256: Option<String>,
+}
 
 // Common trait for all unlockers (CreamLinux, SmokeAPI)
 #[async_trait]
 pub trait Unlocker {
     // Get the latest version from the remote source
-    async fn get_latest_version() -> Result<String, String>;
+    async fn get_latest_version() -> Result<String, UnlockerError>;
+
+    // Confirm a pinned release tag actually exists upstream
+    async fn verify_release_exists(tag: &str) -> Result<(), UnlockerError>;
 
-    // Download the unlocker to the cache directory
-    async fn download_to_cache() -> Result<String, String>;
+    // Resolve which version should actually be installed: the pinned tag if
+    // `pin` is `Some` (after confirming it exists), otherwise whatever
+    // `get_latest_version` reports. This is what lets a user stay on a known
+    // version instead of always tracking latest. There is no release-channel
+    // selection: without a pin, this is always upstream's latest release.
+    async fn resolve_target_version(pin: Option<&str>) -> Result<String, UnlockerError> {
+        match pin {
+            Some(tag) => {
+                Self::verify_release_exists(tag).await?;
+                Ok(tag.to_string())
+            }
+            None => Self::get_latest_version().await,
+        }
+    }
+
+    // Download `version` to the cache directory, reporting progress on
+    // `progress` if the caller provided one
+    async fn download_to_cache(
+        version: &str,
+        progress: Option<ProgressSender>,
+    ) -> Result<VerifiedVersion, UnlockerError>;
 
     // Install the unlocker from cache to a game directory
-    async fn install_to_game(game_path: &str, context: &str) -> Result<(), String>;
+    async fn install_to_game(game_path: &str, context: &str) -> Result<(), UnlockerError>;
 
     // Uninstall the unlocker from a game directory
-    async fn uninstall_from_game(game_path: &str, context: &str) -> Result<(), String>;
+    async fn uninstall_from_game(game_path: &str, context: &str) -> Result<(), UnlockerError>;
 
     // Get the name of the unlocker
     #[allow(dead_code)]
     fn name() -> &'static str;
-}
\ No newline at end of file
+}
+
+// Stream a download response straight to `dest_path`, emitting a
+// `DownloadProgress` after each chunk if the caller supplied a sender.
+// Hashes the archive as it arrives so the whole thing is never held in
+// memory at once, and the caller doesn't need a second pass over the file
+// just to get its digest. Shared by both unlocker implementations so they
+// stay in lockstep on how progress is reported.
+pub(crate) async fn stream_download_body(
+    response: reqwest::Response,
+    component: &'static str,
+    version: String,
+    dest_path: &Path,
+    progress: Option<ProgressSender>,
+) -> Result<String, UnlockerError> {
+    let total_bytes = response.content_length();
+    let mut downloaded_bytes = 0u64;
+    let mut stream = response.bytes_stream();
+    let mut file = std::fs::File::create(dest_path)?;
+    let mut hasher = Sha256::new();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        downloaded_bytes += chunk.len() as u64;
+        hasher.update(&chunk);
+        file.write_all(&chunk)?;
+
+        if let Some(sender) = &progress {
+            let _ = sender.send(DownloadProgress {
+                component,
+                version: version.clone(),
+                downloaded_bytes,
+                total_bytes,
+                message: format_download_message(downloaded_bytes, total_bytes),
+            });
+        }
+    }
+
+    info!(
+        "Downloaded {} bytes for {} {}",
+        downloaded_bytes, component, version
+    );
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
@@ -1,4 +1,4 @@
-use super::Unlocker;
+use super::{stream_download_body, ProgressSender, Unlocker, UnlockerError, VerifiedVersion};
 use async_trait::async_trait;
 use log::{error, info, warn};
 use reqwest;
@@ -9,96 +9,284 @@ use std::time::Duration;
 use tempfile::tempdir;
 use zip::ZipArchive;
 
-const SMOKEAPI_REPO: &str = "acidicoala/SmokeAPI";
+pub(crate) const SMOKEAPI_REPO: &str = "acidicoala/SmokeAPI";
 
-pub struct SmokeAPI;
+// Which release asset to install when a release publishes more than one
+// file (architecture-split zips, a checksums file, etc), matched with a
+// single-wildcard glob against the asset's `name`.
+const DEFAULT_ASSET_PATTERN: &str = "*.zip";
 
-#[async_trait]
-impl Unlocker for SmokeAPI {
-    async fn get_latest_version() -> Result<String, String> {
-        info!("Fetching latest SmokeAPI version...");
+pub struct SmokeAPI;
 
-        let client = reqwest::Client::new();
-        let releases_url = format!(
-            "https://api.github.com/repos/{}/releases/latest",
-            SMOKEAPI_REPO
-        );
+impl SmokeAPI {
+    // Fetch a release's full JSON payload: `/releases/latest` when `tag` is
+    // `None`, otherwise the specific tagged release, so pinning a version or
+    // opting into a prerelease both go through the same lookup.
+    async fn fetch_release(
+        client: &reqwest::Client,
+        tag: Option<&str>,
+    ) -> Result<serde_json::Value, UnlockerError> {
+        let url = match tag {
+            Some(tag) => format!(
+                "https://api.github.com/repos/{}/releases/tags/{}",
+                SMOKEAPI_REPO, tag
+            ),
+            None => format!(
+                "https://api.github.com/repos/{}/releases/latest",
+                SMOKEAPI_REPO
+            ),
+        };
 
         let response = client
-            .get(&releases_url)
+            .get(&url)
             .header("User-Agent", "CreamLinux")
             .timeout(Duration::from_secs(10))
             .send()
-            .await
-            .map_err(|e| format!("Failed to fetch SmokeAPI releases: {}", e))?;
+            .await?;
 
         if !response.status().is_success() {
-            return Err(format!(
-                "Failed to fetch SmokeAPI releases: HTTP {}",
+            return Err(UnlockerError::Http(format!(
+                "Failed to fetch SmokeAPI release: HTTP {}",
                 response.status()
-            ));
+            )));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    // Pick the asset to download: the first whose name matches `pattern`
+    // (defaulting to `DEFAULT_ASSET_PATTERN`), instead of assuming upstream
+    // always names it `SmokeAPI-{tag}.zip`. Errors list the assets the
+    // release actually published, so a naming change upstream is obvious
+    // from the error rather than a generic 404.
+    fn select_asset<'a>(
+        assets: &'a [serde_json::Value],
+        pattern: Option<&str>,
+    ) -> Result<&'a serde_json::Value, UnlockerError> {
+        let pattern = pattern.unwrap_or(DEFAULT_ASSET_PATTERN);
+        assets
+            .iter()
+            .find(|asset| {
+                asset
+                    .get("name")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|name| glob_match(pattern, name))
+            })
+            .ok_or_else(|| {
+                let available: Vec<&str> = assets
+                    .iter()
+                    .filter_map(|a| a.get("name").and_then(|v| v.as_str()))
+                    .collect();
+                UnlockerError::Other(format!(
+                    "No SmokeAPI release asset matches '{}'; available assets: [{}]",
+                    pattern,
+                    available.join(", ")
+                ))
+            })
+    }
+
+    // Verify an already-downloaded archive's digest against the release's
+    // checksums asset (if it published one) before it's extracted anywhere.
+    // Releases that don't publish one log a warning and are allowed
+    // through, since most SmokeAPI releases historically don't.
+    // Returns the SHA-256 the archive was verified against, so the caller
+    // can persist it alongside the cached version — `None` when the release
+    // published nothing to verify against at all.
+    async fn verify_download_integrity(
+        client: &reqwest::Client,
+        assets: &[serde_json::Value],
+        version: &str,
+        zip_name: &str,
+        actual: &str,
+    ) -> Result<Option<String>, UnlockerError> {
+        // Prefer the digest GitHub publishes on the asset itself - it's
+        // always available for an asset that has one, without needing a
+        // second request for a separate checksums file.
+        if let Some(expected) = crate::checksum::find_asset_digest_sha256(assets, zip_name) {
+            if !actual.eq_ignore_ascii_case(&expected) {
+                return Err(UnlockerError::ChecksumMismatch(format!(
+                    "{} — refusing to extract a possibly corrupted or tampered archive",
+                    zip_name
+                )));
+            }
+            info!("Verified SmokeAPI {} archive digest", version);
+            return Ok(Some(actual.to_string()));
+        }
+
+        let Some(checksums_url) = crate::checksum::find_checksums_asset_url(assets) else {
+            warn!(
+                "SmokeAPI {} release does not publish a checksums file; skipping integrity verification",
+                version
+            );
+            return Ok(None);
+        };
+
+        let checksums = client
+            .get(&checksums_url)
+            .timeout(Duration::from_secs(10))
+            .send()
+            .await?
+            .text()
+            .await?;
+
+        match crate::checksum::verify_digest_against_checksums(actual, &checksums, zip_name) {
+            Some(true) => {
+                info!("Verified SmokeAPI {} archive checksum", version);
+                Ok(Some(actual.to_string()))
+            }
+            Some(false) => Err(UnlockerError::ChecksumMismatch(format!(
+                "{} — refusing to extract a possibly corrupted or tampered archive",
+                zip_name
+            ))),
+            None => {
+                warn!(
+                    "No checksum entry for {} in SmokeAPI {} release; skipping integrity verification",
+                    zip_name, version
+                );
+                Ok(None)
+            }
         }
+    }
+}
+
+// Minimal glob match supporting a single `*` wildcard (e.g. `*.zip` or
+// `SmokeAPI-*-64.zip`). Asset name patterns don't need more than that, and
+// it avoids pulling in a full glob crate for one comparison.
+fn glob_match(pattern: &str, name: &str) -> bool {
+    match pattern.split_once('*') {
+        Some((prefix, suffix)) => {
+            name.len() >= prefix.len() + suffix.len()
+                && name.starts_with(prefix)
+                && name.ends_with(suffix)
+        }
+        None => name == pattern,
+    }
+}
+
+#[async_trait]
+impl Unlocker for SmokeAPI {
+    async fn get_latest_version() -> Result<String, UnlockerError> {
+        info!("Fetching latest SmokeAPI version...");
 
-        let release_info: serde_json::Value = response
-            .json()
-            .await
-            .map_err(|e| format!("Failed to parse release info: {}", e))?;
+        let client = reqwest::Client::new();
+        let release_info = Self::fetch_release(&client, None).await?;
 
         let version = release_info
             .get("tag_name")
             .and_then(|v| v.as_str())
-            .ok_or_else(|| "Failed to extract version from release info".to_string())?
+            .ok_or_else(|| UnlockerError::Other("Failed to extract version from release info".to_string()))?
             .to_string();
 
         info!("Latest SmokeAPI version: {}", version);
         Ok(version)
     }
 
-    async fn download_to_cache() -> Result<String, String> {
-        let version = Self::get_latest_version().await?;
+    async fn verify_release_exists(tag: &str) -> Result<(), UnlockerError> {
+        let client = reqwest::Client::new();
+        let release_url = format!(
+            "https://api.github.com/repos/{}/releases/tags/{}",
+            SMOKEAPI_REPO, tag
+        );
+
+        let response = client
+            .get(&release_url)
+            .header("User-Agent", "CreamLinux")
+            .timeout(Duration::from_secs(10))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(UnlockerError::VersionNotFound(format!(
+                "SmokeAPI release {} does not exist",
+                tag
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn download_to_cache(
+        version: &str,
+        progress: Option<ProgressSender>,
+    ) -> Result<VerifiedVersion, UnlockerError> {
+        let version = version.to_string();
         info!("Downloading SmokeAPI version {}...", version);
 
         let client = reqwest::Client::new();
-        let zip_url = format!(
-            "https://github.com/{}/releases/download/{}/SmokeAPI-{}.zip",
-            SMOKEAPI_REPO, version, version
-        );
+        let release_info = Self::fetch_release(&client, Some(&version)).await?;
+        let assets = release_info
+            .get("assets")
+            .and_then(|a| a.as_array())
+            .cloned()
+            .unwrap_or_default();
+
+        // Resolve the actual asset to download from the release itself
+        // instead of guessing its name, so an upstream rename or an
+        // architecture-split zip doesn't silently 404.
+        let asset = Self::select_asset(&assets, None)?;
+        let zip_name = asset
+            .get("name")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| UnlockerError::Other("SmokeAPI release asset has no name".to_string()))?
+            .to_string();
+        let zip_url = asset
+            .get("browser_download_url")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| {
+                UnlockerError::Other(format!("Asset {} has no download URL", zip_name))
+            })?
+            .to_string();
+        let expected_size = asset.get("size").and_then(|v| v.as_u64());
 
         // Download the zip
         let response = client
             .get(&zip_url)
             .timeout(Duration::from_secs(30))
             .send()
-            .await
-            .map_err(|e| format!("Failed to download SmokeAPI: {}", e))?;
+            .await?;
 
         if !response.status().is_success() {
-            return Err(format!(
+            return Err(UnlockerError::Http(format!(
                 "Failed to download SmokeAPI: HTTP {}",
                 response.status()
-            ));
+            )));
         }
 
-        // Save to temporary file
-        let temp_dir = tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+        // Stream straight to a temporary file instead of buffering the
+        // whole archive in memory.
+        let temp_dir = tempdir()?;
         let zip_path = temp_dir.path().join("smokeapi.zip");
-        let content = response
-            .bytes()
-            .await
-            .map_err(|e| format!("Failed to read response bytes: {}", e))?;
-        fs::write(&zip_path, &content).map_err(|e| format!("Failed to write zip file: {}", e))?;
+        let digest =
+            stream_download_body(response, "SmokeAPI", version.clone(), &zip_path, progress)
+                .await?;
+
+        if let Some(expected) = expected_size {
+            let downloaded = fs::metadata(&zip_path)?.len();
+            if downloaded != expected {
+                warn!(
+                    "SmokeAPI {} download is {} bytes; GitHub reported {} for {}",
+                    version, downloaded, expected, zip_name
+                );
+            }
+        }
+
+        info!("Verifying download integrity for SmokeAPI {}...", version);
+        let sha256 =
+            Self::verify_download_integrity(&client, &assets, &version, &zip_name, &digest)
+                .await?;
 
         // Extract to cache directory
-        let version_dir = crate::cache::get_smokeapi_version_dir(&version)?;
-        let file = fs::File::open(&zip_path).map_err(|e| format!("Failed to open zip: {}", e))?;
+        let version_dir = crate::cache::get_smokeapi_version_dir(&version)
+            .map_err(UnlockerError::Other)?;
+        let file = fs::File::open(&zip_path)?;
         let mut archive =
-            ZipArchive::new(file).map_err(|e| format!("Failed to read zip archive: {}", e))?;
+            ZipArchive::new(file).map_err(|e| UnlockerError::ZipExtraction(e.to_string()))?;
 
         // Extract all DLL files
         for i in 0..archive.len() {
             let mut file = archive
                 .by_index(i)
-                .map_err(|e| format!("Failed to access zip entry: {}", e))?;
+                .map_err(|e| UnlockerError::ZipExtraction(e.to_string()))?;
 
             let file_name = file.name();
 
@@ -110,10 +298,9 @@ impl Unlocker for SmokeAPI {
                         .unwrap_or_else(|| std::ffi::OsStr::new(file_name)),
                 );
 
-                let mut outfile = fs::File::create(&output_path)
-                    .map_err(|e| format!("Failed to create output file: {}", e))?;
+                let mut outfile = fs::File::create(&output_path)?;
                 io::copy(&mut file, &mut outfile)
-                    .map_err(|e| format!("Failed to extract file: {}", e))?;
+                    .map_err(|e| UnlockerError::ZipExtraction(e.to_string()))?;
 
                 info!("Extracted: {}", output_path.display());
             }
@@ -123,10 +310,10 @@ impl Unlocker for SmokeAPI {
             "SmokeAPI version {} downloaded to cache successfully",
             version
         );
-        Ok(version)
+        Ok(VerifiedVersion { version, sha256 })
     }
 
-    async fn install_to_game(game_path: &str, api_files_str: &str) -> Result<(), String> {
+    async fn install_to_game(game_path: &str, api_files_str: &str) -> Result<(), UnlockerError> {
         // Parse api_files from the context string (comma-separated)
         let api_files: Vec<String> = api_files_str.split(',').map(|s| s.to_string()).collect();
 
@@ -136,75 +323,124 @@ impl Unlocker for SmokeAPI {
             api_files.len()
         );
 
+        if let Some(incomplete) =
+            crate::installer::detect_incomplete_install(game_path, "smokeapi")
+        {
+            warn!(
+                "Found an incomplete SmokeAPI install for {} ({} file(s) touched, from {}); re-running the install to repair it",
+                game_path,
+                incomplete.files_touched,
+                incomplete
+                    .source
+                    .map(|s| s.version)
+                    .unwrap_or_else(|| "an unknown version".to_string())
+            );
+        }
+
         // Get the cached SmokeAPI DLLs
-        let cached_dlls = crate::cache::list_smokeapi_dlls()?;
+        let cached_dlls = crate::cache::list_smokeapi_dlls().map_err(UnlockerError::Other)?;
         if cached_dlls.is_empty() {
-            return Err("No SmokeAPI DLLs found in cache".to_string());
+            return Err(UnlockerError::NotCached {
+                what: "SmokeAPI DLLs",
+            });
         }
 
-        for api_file in &api_files {
-            let api_dir = Path::new(game_path).join(
-                Path::new(api_file)
-                    .parent()
-                    .unwrap_or_else(|| Path::new("")),
-            );
-            let api_name = Path::new(api_file).file_name().unwrap_or_default();
-
-            // Backup original file
-            let original_path = api_dir.join(api_name);
-            let backup_path = api_dir.join(api_name.to_string_lossy().replace(".dll", "_o.dll"));
-
-            info!("Processing: {}", original_path.display());
-
-            // Only backup if not already backed up
-            if !backup_path.exists() && original_path.exists() {
-                fs::copy(&original_path, &backup_path)
-                    .map_err(|e| format!("Failed to backup original file: {}", e))?;
-                info!("Created backup: {}", backup_path.display());
+        let version = crate::cache::read_versions()
+            .map_err(UnlockerError::Other)?
+            .smokeapi
+            .active;
+
+        // Record every file written (backing up anything it clobbers) so a
+        // failure partway through - or a crash - can be recovered from
+        // instead of leaving the game with no valid API DLL and no record
+        // of what was touched.
+        let mut transaction =
+            crate::installer::InstallTransaction::new(game_path, "smokeapi").with_source(version);
+
+        let result = (|| -> Result<(), UnlockerError> {
+            for api_file in &api_files {
+                let api_dir = Path::new(game_path).join(
+                    Path::new(api_file)
+                        .parent()
+                        .unwrap_or_else(|| Path::new("")),
+                );
+                let api_name = Path::new(api_file).file_name().unwrap_or_default();
+                let original_path = api_dir.join(api_name);
+
+                info!("Processing: {}", original_path.display());
+
+                // Determine if we need 32-bit or 64-bit SmokeAPI DLL
+                let is_64bit = api_name.to_string_lossy().contains("64");
+                let target_arch = if is_64bit { "64" } else { "32" };
+
+                // Find the matching DLL
+                let matching_dll = cached_dlls
+                    .iter()
+                    .find(|dll| {
+                        let dll_name = dll.file_name().unwrap_or_default().to_string_lossy();
+                        dll_name.to_lowercase().contains("smoke")
+                            && dll_name
+                                .to_lowercase()
+                                .contains(&format!("{}.dll", target_arch))
+                    })
+                    .ok_or(UnlockerError::MissingArchDll { arch: target_arch })?;
+
+                let contents = fs::read(matching_dll)?;
+                transaction.write_file_as(
+                    &original_path,
+                    matching_dll.file_name().and_then(|n| n.to_str()),
+                    &contents,
+                )?;
+
+                info!(
+                    "Installed {} as: {}",
+                    matching_dll.display(),
+                    original_path.display()
+                );
+            }
+            Ok(())
+        })();
+
+        match result {
+            Ok(()) => transaction.commit().map_err(UnlockerError::Other)?,
+            Err(e) => {
+                transaction.rollback();
+                return Err(e);
             }
-
-            // Determine if we need 32-bit or 64-bit SmokeAPI DLL
-            let is_64bit = api_name.to_string_lossy().contains("64");
-            let target_arch = if is_64bit { "64" } else { "32" };
-
-            // Find the matching DLL
-            let matching_dll = cached_dlls
-                .iter()
-                .find(|dll| {
-                    let dll_name = dll.file_name().unwrap_or_default().to_string_lossy();
-                    dll_name.to_lowercase().contains("smoke")
-                        && dll_name
-                            .to_lowercase()
-                            .contains(&format!("{}.dll", target_arch))
-                })
-                .ok_or_else(|| {
-                    format!(
-                        "No matching {}-bit SmokeAPI DLL found in cache",
-                        target_arch
-                    )
-                })?;
-
-            // Copy the DLL to the game directory
-            fs::copy(matching_dll, &original_path)
-                .map_err(|e| format!("Failed to install SmokeAPI DLL: {}", e))?;
-
-            info!(
-                "Installed {} as: {}",
-                matching_dll.display(),
-                original_path.display()
-            );
         }
 
         info!("SmokeAPI installation completed for: {}", game_path);
         Ok(())
     }
 
-    async fn uninstall_from_game(game_path: &str, api_files_str: &str) -> Result<(), String> {
-        // Parse api_files from the context string (comma-separated)
-        let api_files: Vec<String> = api_files_str.split(',').map(|s| s.to_string()).collect();
-
+    async fn uninstall_from_game(
+        game_path: &str,
+        api_files_str: &str,
+    ) -> Result<(), UnlockerError> {
         info!("Uninstalling SmokeAPI from: {}", game_path);
 
+        // Restore strictly from the install manifest rather than guessing a
+        // backup's name via the `_o.dll` convention, which never round-
+        // tripped differently-cased or non-standard API DLL names.
+        match crate::installer::uninstall_via_manifest(game_path, "smokeapi") {
+            Ok(true) => {
+                info!("SmokeAPI uninstallation completed for: {}", game_path);
+                return Ok(());
+            }
+            Ok(false) => {
+                info!(
+                    "No install manifest for {} (pre-dates manifest tracking), falling back to the `_o.dll` convention",
+                    game_path
+                );
+            }
+            Err(e) => {
+                warn!("Failed to uninstall SmokeAPI via manifest: {}", e);
+            }
+        }
+
+        // Legacy fallback for installs made before manifest tracking existed
+        let api_files: Vec<String> = api_files_str.split(',').map(|s| s.to_string()).collect();
+
         for api_file in &api_files {
             let api_path = Path::new(game_path).join(api_file);
             let api_dir = api_path.parent().unwrap_or_else(|| Path::new(game_path));
@@ -246,7 +482,9 @@ impl Unlocker for SmokeAPI {
                     }
                 }
             } else {
-                info!("No backup found for: {}", api_file);
+                return Err(UnlockerError::BackupMissing {
+                    path: backup_path.display().to_string(),
+                });
             }
         }
 
@@ -257,4 +495,38 @@ impl Unlocker for SmokeAPI {
     fn name() -> &'static str {
         "SmokeAPI"
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match_wildcard_suffix() {
+        assert!(glob_match("*.zip", "SmokeAPI-v1.0.0.zip"));
+        assert!(!glob_match("*.zip", "SmokeAPI-v1.0.0.tar.gz"));
+    }
+
+    #[test]
+    fn test_glob_match_no_wildcard_requires_exact() {
+        assert!(glob_match("SmokeAPI.zip", "SmokeAPI.zip"));
+        assert!(!glob_match("SmokeAPI.zip", "SmokeAPI-64.zip"));
+    }
+
+    #[test]
+    fn test_select_asset_picks_first_match() {
+        let assets = serde_json::json!([
+            {"name": "SmokeAPI-v1.0.0.zip", "browser_download_url": "https://example.com/a.zip"},
+            {"name": "checksums.sha256", "browser_download_url": "https://example.com/a.sha256"},
+        ]);
+        let asset = SmokeAPI::select_asset(assets.as_array().unwrap(), None).unwrap();
+        assert_eq!(asset.get("name").and_then(|v| v.as_str()), Some("SmokeAPI-v1.0.0.zip"));
+    }
+
+    #[test]
+    fn test_select_asset_reports_available_names_on_miss() {
+        let assets = serde_json::json!([{"name": "SmokeAPI.tar.gz"}]);
+        let err = SmokeAPI::select_asset(assets.as_array().unwrap(), None).unwrap_err();
+        assert!(err.to_string().contains("SmokeAPI.tar.gz"));
+    }
+}
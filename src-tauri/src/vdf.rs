@@ -0,0 +1,162 @@
+// Minimal recursive-descent parser for Valve's VDF (KeyValues) format, used
+// to read appmanifest ACF files, libraryfolders.vdf and Steam's registry/
+// config VDFs without relying on regexes that can't safely see nesting.
+
+use std::collections::HashMap;
+
+// A parsed VDF node: either a leaf string value or a nested block of
+// key/value pairs. Pairs are kept in a Vec (not a HashMap) since VDF allows
+// duplicate keys and preserves declaration order.
+#[derive(Debug, Clone)]
+pub enum VdfValue {
+    Str(String),
+    Block(Vec<(String, VdfValue)>),
+}
+
+impl VdfValue {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            VdfValue::Str(s) => Some(s),
+            VdfValue::Block(_) => None,
+        }
+    }
+
+    pub fn as_block(&self) -> Option<&[(String, VdfValue)]> {
+        match self {
+            VdfValue::Block(entries) => Some(entries),
+            VdfValue::Str(_) => None,
+        }
+    }
+
+    // First value for `key` in this block, if any
+    pub fn get(&self, key: &str) -> Option<&VdfValue> {
+        self.as_block()?
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v)
+    }
+
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.get(key)?.as_str()
+    }
+
+    // Every (key, value) pair in this block, case-sensitive
+    pub fn entries(&self) -> &[(String, VdfValue)] {
+        self.as_block().unwrap_or(&[])
+    }
+}
+
+// Parse a whole VDF document into a synthetic root block holding its
+// top-level key(s) (typically a single `"AppState" { ... }`-style entry)
+pub fn parse(content: &str) -> VdfValue {
+    let bytes = content.as_bytes();
+    let mut pos = 0;
+    VdfValue::Block(parse_entries(bytes, &mut pos))
+}
+
+fn skip_ws_and_comments(bytes: &[u8], pos: &mut usize) {
+    loop {
+        while matches!(bytes.get(*pos), Some(b) if b.is_ascii_whitespace()) {
+            *pos += 1;
+        }
+
+        if bytes.get(*pos) == Some(&b'/') && bytes.get(*pos + 1) == Some(&b'/') {
+            while matches!(bytes.get(*pos), Some(b) if *b != b'\n') {
+                *pos += 1;
+            }
+            continue;
+        }
+
+        break;
+    }
+}
+
+// Parse a quoted string starting at `bytes[*pos] == '"'`, handling `\"` and
+// `\\` escapes. Returns None if the string is unterminated.
+fn parse_quoted(bytes: &[u8], pos: &mut usize) -> Option<String> {
+    if bytes.get(*pos) != Some(&b'"') {
+        return None;
+    }
+    *pos += 1;
+
+    let mut buf = Vec::new();
+    while let Some(&b) = bytes.get(*pos) {
+        match b {
+            b'\\' => {
+                if let Some(&escaped) = bytes.get(*pos + 1) {
+                    buf.push(escaped);
+                    *pos += 2;
+                } else {
+                    *pos += 1;
+                }
+            }
+            b'"' => {
+                *pos += 1;
+                return Some(String::from_utf8_lossy(&buf).into_owned());
+            }
+            _ => {
+                buf.push(b);
+                *pos += 1;
+            }
+        }
+    }
+
+    None
+}
+
+// Parse key/value pairs until either EOF or a closing `}` (consumed here)
+fn parse_entries(bytes: &[u8], pos: &mut usize) -> Vec<(String, VdfValue)> {
+    let mut entries = Vec::new();
+
+    loop {
+        skip_ws_and_comments(bytes, pos);
+
+        match bytes.get(*pos) {
+            None => break,
+            Some(b'}') => {
+                *pos += 1;
+                break;
+            }
+            Some(b'"') => {
+                let key = match parse_quoted(bytes, pos) {
+                    Some(k) => k,
+                    None => break,
+                };
+
+                skip_ws_and_comments(bytes, pos);
+
+                match bytes.get(*pos) {
+                    Some(b'"') => {
+                        if let Some(value) = parse_quoted(bytes, pos) {
+                            entries.push((key, VdfValue::Str(value)));
+                        }
+                    }
+                    Some(b'{') => {
+                        *pos += 1;
+                        entries.push((key, VdfValue::Block(parse_entries(bytes, pos))));
+                    }
+                    _ => {
+                        // Malformed entry (e.g. key with no value); skip it
+                    }
+                }
+            }
+            Some(_) => {
+                // Unexpected token outside a key position; skip past it
+                *pos += 1;
+            }
+        }
+    }
+
+    entries
+}
+
+// Flatten a block's direct string-valued children into a map, ignoring any
+// nested blocks. Handy for leaf sections like a `CompatToolMapping` entry's
+// `{ "name" "proton_9" ... }` body.
+pub fn flatten_strings(value: &VdfValue) -> HashMap<String, String> {
+    value
+        .entries()
+        .iter()
+        .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
+        .collect()
+}